@@ -0,0 +1,53 @@
+#![allow(clippy::unwrap_used, clippy::print_stdout)]
+
+use std::num::NonZeroU32;
+
+use rugfx::gfx::{Gfx, GfxConfig};
+use winit::dpi::PhysicalSize;
+
+fn main() {
+    let size = PhysicalSize::new(100, 50);
+    let config = GfxConfig {
+        // Headless rendering doesn't need any optional features, which keeps software adapters usable
+        required_features: wgpu::Features::empty(),
+        ..Default::default()
+    };
+    let mut gfx = Gfx::new_from_buffer(size, &config).unwrap();
+    let pixels = clear(&gfx);
+    println!(
+        "Rendered {}x{} pixels headlessly, the first is {:?}",
+        size.width,
+        size.height,
+        &pixels[..4]
+    );
+
+    // Minimizing delivers a zero size which must be ignored until the next real resize
+    let count = gfx.set_multisample_count(NonZeroU32::new(4).unwrap());
+    gfx.window_resize(&PhysicalSize::new(0, 0));
+    let restored = PhysicalSize::new(60, 20);
+    gfx.window_resize(&restored);
+    clear(&gfx);
+    println!(
+        "Resized to {}x{} with {count}x msaa",
+        restored.width, restored.height
+    );
+}
+
+/// Clears the buffer to red and reads back its pixels
+fn clear(gfx: &Gfx) -> Vec<u8> {
+    let texture = gfx.get_current_texture().unwrap();
+    let view = texture
+        .texture()
+        .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = gfx.create_encoder(Some("clear"));
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(gfx.clear_attachment(wgpu::Color::RED, &view).unwrap())],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    gfx.submit(Some(encoder.finish()));
+    gfx.present().unwrap();
+    gfx.read_pixels().unwrap()
+}
@@ -0,0 +1,98 @@
+#![allow(clippy::unwrap_used, clippy::print_stdout)]
+
+use rugfx::gfx::{Gfx, GfxConfig};
+use winit::dpi::PhysicalSize;
+
+const SIZE: u32 = 64;
+
+fn main() {
+    let config = GfxConfig {
+        required_features: wgpu::Features::empty(),
+        ..Default::default()
+    };
+    let gfx = Gfx::new_from_buffer(PhysicalSize::new(1, 1), &config).unwrap();
+
+    let extent = wgpu::Extent3d {
+        width: SIZE,
+        height: SIZE,
+        depth_or_array_layers: 1,
+    };
+    let texture = gfx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("checkerboard"),
+        size: extent,
+        mip_level_count: extent.max_mips(wgpu::TextureDimension::D2),
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    // A checkerboard of single black and white pixels averages to grey in every smaller level
+    let pixels: Vec<u8> = (0..SIZE * SIZE)
+        .flat_map(|i| {
+            let value = if (i % SIZE + i / SIZE).is_multiple_of(2) {
+                255
+            } else {
+                0
+            };
+            [value, value, value, 255]
+        })
+        .collect();
+    gfx.queue.write_texture(
+        texture.as_image_copy(),
+        &pixels,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * SIZE),
+            rows_per_image: None,
+        },
+        extent,
+    );
+    gfx.generate_mipmaps(&texture).unwrap();
+
+    // Read back the 1x1 smallest level
+    let buffer = gfx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("smallest mip"),
+        size: 4,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = gfx.create_encoder(Some("read smallest mip"));
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: texture.mip_level_count() - 1,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout::default(),
+        },
+        wgpu::Extent3d::default(),
+    );
+    gfx.submit(Some(encoder.finish()));
+    buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+    gfx.device.poll(wgpu::Maintain::Wait);
+    let pixel = buffer.slice(..).get_mapped_range().to_vec();
+
+    println!(
+        "smallest of {} mip levels is {pixel:?}",
+        texture.mip_level_count()
+    );
+    assert!(
+        (120..=135).contains(&pixel[0]),
+        "mip levels were not averaged"
+    );
+    assert_eq!(pixel[3], 255);
+
+    let buffer_texture = gfx.buffer_texture().unwrap();
+    assert!(
+        gfx.generate_mipmaps(buffer_texture).is_err(),
+        "the buffer texture can't be sampled"
+    );
+}
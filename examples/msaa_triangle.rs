@@ -0,0 +1,93 @@
+#![allow(clippy::unwrap_used, clippy::print_stdout)]
+
+use std::num::NonZeroU32;
+
+use rugfx::gfx::{Gfx, GfxConfig};
+use winit::dpi::PhysicalSize;
+
+const SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the bottom left half of the screen, its diagonal edge crosses pixels partially
+    var positions = array<vec2<f32>, 3>(vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0));
+    return vec4(positions[index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4(1.0, 1.0, 1.0, 1.0);
+}
+";
+
+fn main() {
+    let size = PhysicalSize::new(64, 64);
+    let config = GfxConfig {
+        required_features: wgpu::Features::empty(),
+        multisample_count: NonZeroU32::new(4).unwrap(),
+        ..Default::default()
+    };
+    let gfx = Gfx::new_from_buffer(size, &config).unwrap();
+    println!("Rendering with {}x msaa", gfx.multisample_count);
+
+    let shader = gfx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("triangle shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    let pipeline = gfx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("triangle pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(gfx.config.format.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            // The pipeline must match the multisample texture that clear_attachment renders to
+            multisample: wgpu::MultisampleState {
+                count: gfx.multisample_count.get(),
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+    let texture = gfx.get_current_texture().unwrap();
+    let view = texture
+        .texture()
+        .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = gfx.create_encoder(Some("triangle"));
+    {
+        // The multisampled result is resolved into the buffer texture at the end of the pass
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(
+                gfx.clear_attachment(wgpu::Color::BLACK, &view).unwrap(),
+            )],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.draw(0..3, 0..1);
+    }
+    gfx.submit(Some(encoder.finish()));
+
+    let (pixels, _, _) = gfx.capture_rgba().unwrap();
+    let count = |f: fn(u8) -> bool| pixels.chunks_exact(4).filter(|p| f(p[0])).count();
+    let covered = count(|r| r == 255);
+    let empty = count(|r| r == 0);
+    let edges = count(|r| r > 0 && r < 255);
+    println!("{covered} covered, {empty} empty and {edges} anti-aliased edge pixels");
+}
@@ -0,0 +1,99 @@
+#![allow(clippy::unwrap_used, clippy::print_stdout)]
+
+use rugfx::{
+    gfx::{Gfx, GfxConfig},
+    input::raw::{RawInputHandler, RawInputManager, RawInputManagerState},
+};
+use winit::{keyboard::KeyCode, window::Window};
+
+const COLORS: [wgpu::Color; 3] = [
+    wgpu::Color {
+        r: 0.1,
+        g: 0.1,
+        b: 0.1,
+        a: 1.0,
+    },
+    wgpu::Color {
+        r: 0.1,
+        g: 0.2,
+        b: 0.4,
+        a: 1.0,
+    },
+    wgpu::Color {
+        r: 0.4,
+        g: 0.1,
+        b: 0.2,
+        a: 1.0,
+    },
+];
+
+#[derive(Default)]
+struct App {
+    gfx: Option<Gfx>,
+    color_index: usize,
+}
+
+impl RawInputHandler for App {
+    fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        // The window can only be created once the event loop has resumed
+        let attributes = Window::default_attributes().with_title("rugfx simple gfx");
+        self.gfx = Some(
+            Gfx::new_from_window_attributes(event_loop, attributes, &GfxConfig::default()).unwrap(),
+        );
+    }
+
+    fn update(
+        &mut self,
+        event_loop: &winit::event_loop::ActiveEventLoop,
+        input: &RawInputManagerState,
+    ) {
+        let Some(gfx) = &mut self.gfx else {
+            return;
+        };
+        if let Some(size) = input.resized() {
+            gfx.window_resize(size);
+        }
+        if input.pressed(&KeyCode::Space.into()) {
+            self.color_index = (self.color_index + 1) % COLORS.len();
+            println!("Switched to color {}", self.color_index);
+        }
+        if input.close_requested() || input.pressed(&KeyCode::Escape.into()) {
+            event_loop.exit();
+        }
+    }
+
+    fn draw(
+        &mut self,
+        _event_loop: &winit::event_loop::ActiveEventLoop,
+        _input: &RawInputManagerState,
+    ) {
+        let Some(gfx) = &self.gfx else {
+            return;
+        };
+        let texture = gfx.get_current_texture().unwrap();
+        let view = texture
+            .texture()
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = gfx.create_encoder(Some("clear"));
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(
+                gfx.clear_attachment(COLORS[self.color_index], &view)
+                    .unwrap(),
+            )],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        gfx.submit(Some(encoder.finish()));
+        texture.present();
+    }
+}
+
+fn main() {
+    println!("Press space to change the clear color and escape to quit");
+    let mut input_manager = RawInputManager::new(App::default());
+    let event_loop = winit::event_loop::EventLoop::new().unwrap();
+    event_loop.set_control_flow(winit::event_loop::ControlFlow::Poll);
+    event_loop.run_app(&mut input_manager).unwrap();
+}
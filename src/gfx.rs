@@ -1,16 +1,27 @@
 pub mod buffer;
+mod mipmap;
+mod readback;
 pub mod surface;
 
-use std::{num::NonZeroU32, sync::Arc};
+use std::{
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock, PoisonError,
+    },
+};
 
 use buffer::GfxBuffer;
+use mipmap::MipmapGenerator;
+use readback::Readback;
 use surface::GfxSurface;
 use thiserror::Error;
 use wgpu::{Backends, CreateSurfaceError, RequestDeviceError, SurfaceError, TextureFormat};
 use winit::{
-    dpi::PhysicalSize,
-    error::ExternalError,
-    window::{Fullscreen, Window},
+    dpi::{PhysicalPosition, PhysicalSize},
+    error::{ExternalError, OsError},
+    monitor::VideoModeHandle,
+    window::{CursorGrabMode, CursorIcon, Fullscreen, Window},
 };
 
 #[derive(Debug, Error)]
@@ -19,14 +30,36 @@ pub enum GfxError {
     IOError(#[from] std::io::Error),
     #[error("winit error: {0}")]
     WinitError(#[from] ExternalError),
+    #[error("os error: {0}")]
+    OsError(#[from] OsError),
     #[error("surface error: {0}")]
     SurfaceError(#[from] SurfaceError),
+    #[error("out of gpu memory")]
+    OutOfMemory,
     #[error("create surface error: {0}")]
     CreateSurfaceError(#[from] CreateSurfaceError),
-    #[error("pngs can only be capture from buffers")]
-    CannotCapturePngFromSurface,
+    #[error("pixels can only be read from buffers")]
+    CannotReadPixelsFromSurface,
+    #[error("cannot capture texture format {0:?}")]
+    UnsupportedCaptureFormat(TextureFormat),
+    #[error("texture must have the copy src usage to be captured")]
+    TextureNotCopyable,
+    #[error("cannot read depth from texture format {0:?}")]
+    UnsupportedDepthFormat(TextureFormat),
+    #[error("the adapter does not support copying depth textures")]
+    DepthReadbackUnsupported,
+    #[error("mipmaps can only be generated for 2d textures with the render attachment and texture binding usages")]
+    TextureNotMipmappable,
+    #[error("cannot generate mipmaps for texture format {0:?}")]
+    UnsupportedMipmapFormat(TextureFormat),
+    #[error("pixel ({0}, {1}) is outside the texture")]
+    PixelOutOfBounds(u32, u32),
+    #[error("buffer map error: {0}")]
+    BufferAsyncError(#[from] wgpu::BufferAsyncError),
     #[error("request adapter error")]
     RequestAdapterError,
+    #[error("no software fallback adapter is available using the backends {0:?}")]
+    FallbackAdapterUnavailable(Backends),
     #[cfg(feature = "capture")]
     #[error("encoding error: {0}")]
     EncodingError(#[from] png::EncodingError),
@@ -38,6 +71,44 @@ pub struct GfxConfig {
     pub present_mode: wgpu::PresentMode,
     pub required_features: wgpu::Features,
     pub multisample_count: NonZeroU32,
+    /// Whether the contents of the multisample texture are kept after resolving, see [`Gfx::multisample_store`]
+    pub multisample_store: wgpu::StoreOp,
+    /// The color buffer backings are cleared to when created, so render passes that load the previous contents are deterministic
+    pub buffer_clear_color: wgpu::Color,
+    /// Prefix for the labels of the wgpu resources created by [`Gfx`], making them easier to identify in graphics debuggers.
+    /// Defaults to [`None`] which leaves them unlabeled.
+    pub label_prefix: Option<&'static str>,
+    /// Limits the maximum texture width and height requested from the adapter. Defaults to [`None`] which uses the largest the
+    /// adapter supports, see [`Gfx::max_texture_dimension`] for the effective value.
+    pub max_texture_dimension: Option<u32>,
+    /// How the alpha channel of captured pixels is interpreted, see [`CaptureAlpha`]
+    pub capture_alpha: CaptureAlpha,
+    /// The graphics APIs the adapter can be chosen from, such as [`Backends::VULKAN`] to force Vulkan. Defaults to WebGL2 on
+    /// wasm and every supported backend otherwise.
+    pub backends: Backends,
+    /// Whether to prefer the discrete or integrated GPU when there is more than one. Defaults to
+    /// [`wgpu::PowerPreference::HighPerformance`], use [`wgpu::PowerPreference::LowPower`] to save battery on laptops.
+    pub power_preference: wgpu::PowerPreference,
+    /// The surface texture format to use if the surface supports it. Defaults to [`None`] which picks the first sRGB format the
+    /// surface supports, or the first format if there is none. Buffer backings always use [`TextureFormat::Rgba8UnormSrgb`].
+    pub preferred_format: Option<TextureFormat>,
+    /// Only use a software adapter such as lavapipe, llvmpipe or WARP, so tests render the same on machines without a GPU.
+    /// Defaults to false. If none is installed for the [`Self::backends`] [`GfxError::FallbackAdapterUnavailable`] is
+    /// returned.
+    pub force_fallback_adapter: bool,
+}
+
+/// How the alpha channel is treated when reading back pixels with [`Gfx::read_pixels`] or writing pngs.
+///
+/// Blending with [`wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING`] leaves the color premultiplied by the alpha, but png expects
+/// straight alpha so translucent pixels look dark in image editors unless they are unpremultiplied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureAlpha {
+    /// Leave the pixels exactly as rendered
+    #[default]
+    AsIs,
+    /// Divide the color by the alpha, converting premultiplied alpha to straight alpha
+    Unpremultiply,
 }
 
 impl Default for GfxConfig {
@@ -47,10 +118,28 @@ impl Default for GfxConfig {
             required_features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
                 | wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER,
             multisample_count: NonZeroU32::MIN,
+            multisample_store: wgpu::StoreOp::Discard,
+            buffer_clear_color: wgpu::Color::TRANSPARENT,
+            label_prefix: None,
+            max_texture_dimension: None,
+            capture_alpha: CaptureAlpha::AsIs,
+            backends: default_backends(),
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            preferred_format: None,
+            force_fallback_adapter: false,
         }
     }
 }
 
+fn default_backends() -> Backends {
+    // In the browser WebGL2 is used through the GL backend
+    #[cfg(target_arch = "wasm32")]
+    let backends = Backends::GL;
+    #[cfg(not(target_arch = "wasm32"))]
+    let backends = Backends::VULKAN | Backends::METAL | Backends::DX12 | Backends::GL;
+    backends
+}
+
 pub struct Gfx {
     pub backing: GfxBacking,
     pub device: wgpu::Device,
@@ -58,54 +147,141 @@ pub struct Gfx {
     pub config: wgpu::SurfaceConfiguration,
     pub multisample_count: NonZeroU32,
     pub multisample_view: Option<wgpu::TextureView>,
+    /// The store operation used for the multisample attachment in [`Self::color_attachments`].
+    ///
+    /// [`wgpu::StoreOp::Discard`] only keeps the resolved result which saves memory bandwidth, use [`wgpu::StoreOp::Store`]
+    /// to keep the multisampled contents for techniques that accumulate over several passes at the cost of writing the
+    /// full multisample texture back to memory.
+    pub multisample_store: wgpu::StoreOp,
+    /// Prefix for the labels of the wgpu resources created by [`Gfx`], see [`GfxConfig::label_prefix`]
+    pub label_prefix: Option<&'static str>,
+    /// The downlevel capabilities of the adapter, used to check optional operations such as [`Self::read_depth_at`]
+    pub downlevel_flags: wgpu::DownlevelFlags,
+    /// How the alpha channel of captured pixels is interpreted, see [`GfxConfig::capture_alpha`]
+    pub capture_alpha: CaptureAlpha,
+    /// The color buffer backings are cleared to when created or resized, see [`GfxConfig::buffer_clear_color`]
+    pub buffer_clear_color: wgpu::Color,
+    /// The cursor visibility to restore when leaving relative mouse mode
+    cursor_visible: AtomicBool,
+    /// The grab mode last applied to the window, see [`Self::cursor_grab_mode`]
+    cursor_grab_mode: Mutex<CursorGrabMode>,
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    adapter_info: wgpu::AdapterInfo,
+    /// The features of the texture format, used to check which multisample counts are supported
+    sample_flags: wgpu::TextureFormatFeatureFlags,
+    /// Created the first time [`Self::generate_mipmaps`] is called
+    mipmap_generator: OnceLock<MipmapGenerator>,
 }
 
 impl Gfx {
+    /// Creates a [`Gfx`] rendering to a window, blocking until the device is ready.
+    ///
+    /// Not available on wasm where blocking is not possible, use [`Self::new_from_window_async`] instead.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn new_from_window(window: Window, config: &GfxConfig) -> Result<Self, GfxError> {
-        pollster::block_on(async {
-            let instance = Self::create_instance();
-            let window = Arc::new(window);
-            let surface = instance.create_surface(Arc::clone(&window))?;
-            let adapter = instance
-                .request_adapter(&wgpu::RequestAdapterOptionsBase {
-                    power_preference: wgpu::PowerPreference::HighPerformance,
-                    force_fallback_adapter: false,
-                    compatible_surface: Some(&surface),
-                })
-                .await
-                .ok_or(GfxError::RequestAdapterError)?;
-            let (device, queue) = Self::request_device(&adapter, config).await?;
-            let size = window.inner_size();
-            let internal = GfxBacking::Surface(GfxSurface { window, surface });
+        pollster::block_on(Self::new_from_window_async(window, config))
+    }
 
-            Ok(Self::setup(&adapter, device, queue, internal, size, config))
-        })
+    /// Creates a window from `attributes` and a [`Gfx`] rendering to it, blocking until the device is ready.
+    ///
+    /// With winit windows can only be created once the event loop is running so call this from
+    /// [`winit::application::ApplicationHandler::resumed`] or [`crate::input::raw::RawInputHandler::resumed`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rugfx::gfx::{Gfx, GfxConfig};
+    /// use winit::{event_loop::ActiveEventLoop, window::Window};
+    ///
+    /// struct App {
+    ///     gfx: Option<Gfx>,
+    /// }
+    ///
+    /// impl App {
+    ///     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+    ///         let attributes = Window::default_attributes().with_title("My game");
+    ///         self.gfx = Some(
+    ///             Gfx::new_from_window_attributes(event_loop, attributes, &GfxConfig::default()).unwrap(),
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new_from_window_attributes(
+        event_loop: &winit::event_loop::ActiveEventLoop,
+        attributes: winit::window::WindowAttributes,
+        config: &GfxConfig,
+    ) -> Result<Self, GfxError> {
+        let window = event_loop.create_window(attributes)?;
+        Self::new_from_window(window, config)
     }
 
+    /// Creates a [`Gfx`] rendering to a window.
+    pub async fn new_from_window_async(
+        window: Window,
+        config: &GfxConfig,
+    ) -> Result<Self, GfxError> {
+        let instance = Self::create_instance(config);
+        let window = Arc::new(window);
+        let surface = instance.create_surface(Arc::clone(&window))?;
+        let adapter = Self::request_adapter(&instance, Some(&surface), config).await?;
+        let (device, queue) = Self::request_device(&adapter, config).await?;
+        let size = window.inner_size();
+        let internal = GfxBacking::Surface(GfxSurface { window, surface });
+
+        Ok(Self::setup(
+            instance, adapter, device, queue, internal, size, config,
+        ))
+    }
+
+    /// Creates a [`Gfx`] rendering offscreen to a buffer, blocking until the device is ready.
+    ///
+    /// Not available on wasm where blocking is not possible, use [`Self::new_from_buffer_async`] instead.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn new_from_buffer(size: PhysicalSize<u32>, config: &GfxConfig) -> Result<Self, GfxError> {
-        pollster::block_on(async {
-            let instance = Self::create_instance();
-            let adapter = instance
-                .request_adapter(&wgpu::RequestAdapterOptionsBase {
-                    power_preference: wgpu::PowerPreference::HighPerformance,
-                    force_fallback_adapter: false,
-                    compatible_surface: None,
-                })
-                .await
-                .ok_or(GfxError::RequestAdapterError)?;
-            let (device, queue) = Self::request_device(&adapter, config).await?;
-            let internal = GfxBacking::Buffer(GfxBuffer::new(&device, size));
-            Ok(Self::setup(&adapter, device, queue, internal, size, config))
-        })
+        pollster::block_on(Self::new_from_buffer_async(size, config))
     }
 
-    fn create_instance() -> wgpu::Instance {
+    /// Creates a [`Gfx`] rendering offscreen to a buffer.
+    pub async fn new_from_buffer_async(
+        size: PhysicalSize<u32>,
+        config: &GfxConfig,
+    ) -> Result<Self, GfxError> {
+        let instance = Self::create_instance(config);
+        let adapter = Self::request_adapter(&instance, None, config).await?;
+        let (device, queue) = Self::request_device(&adapter, config).await?;
+        let internal = GfxBacking::Buffer(GfxBuffer::new(&device, size, config.label_prefix));
+        let gfx = Self::setup(instance, adapter, device, queue, internal, size, config);
+        gfx.clear_buffer(config.buffer_clear_color);
+        Ok(gfx)
+    }
+
+    fn create_instance(config: &GfxConfig) -> wgpu::Instance {
         wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: Backends::VULKAN | Backends::METAL | Backends::DX12 | Backends::GL,
+            backends: config.backends,
             ..Default::default()
         })
     }
 
+    async fn request_adapter(
+        instance: &wgpu::Instance,
+        compatible_surface: Option<&wgpu::Surface<'_>>,
+        config: &GfxConfig,
+    ) -> Result<wgpu::Adapter, GfxError> {
+        instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: config.power_preference,
+                force_fallback_adapter: config.force_fallback_adapter,
+                compatible_surface,
+            })
+            .await
+            .ok_or(if config.force_fallback_adapter {
+                GfxError::FallbackAdapterUnavailable(config.backends)
+            } else {
+                GfxError::RequestAdapterError
+            })
+    }
+
     async fn request_device(
         adapter: &wgpu::Adapter,
         config: &GfxConfig,
@@ -113,13 +289,9 @@ impl Gfx {
         Ok(adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    label: None,
+                    label: label(config.label_prefix, "device").as_deref(),
                     required_features: config.required_features,
-                    required_limits: wgpu::Limits {
-                        max_texture_dimension_1d: 8192,
-                        max_texture_dimension_2d: 8192,
-                        ..wgpu::Limits::downlevel_defaults()
-                    },
+                    required_limits: Self::required_limits(adapter, config),
                     memory_hints: wgpu::MemoryHints::Performance,
                 },
                 None,
@@ -127,24 +299,48 @@ impl Gfx {
             .await?)
     }
 
+    fn required_limits(adapter: &wgpu::Adapter, config: &GfxConfig) -> wgpu::Limits {
+        #[cfg(target_arch = "wasm32")]
+        let base_limits = wgpu::Limits::downlevel_webgl2_defaults();
+        #[cfg(not(target_arch = "wasm32"))]
+        let base_limits = wgpu::Limits::downlevel_defaults();
+
+        // Request the largest textures the adapter supports rather than the conservative defaults
+        let adapter_limits = adapter.limits();
+        let requested = config.max_texture_dimension.unwrap_or(u32::MAX);
+        let max_texture_dimension_2d = adapter_limits.max_texture_dimension_2d.min(requested);
+        log::debug!("Using max texture dimension {max_texture_dimension_2d}");
+        wgpu::Limits {
+            max_texture_dimension_1d: adapter_limits.max_texture_dimension_1d.min(requested),
+            max_texture_dimension_2d,
+            ..base_limits
+        }
+    }
+
     fn setup(
-        adapter: &wgpu::Adapter,
+        instance: wgpu::Instance,
+        adapter: wgpu::Adapter,
         device: wgpu::Device,
         queue: wgpu::Queue,
         internal: GfxBacking,
         size: PhysicalSize<u32>,
         config: &GfxConfig,
     ) -> Self {
+        let adapter_info = adapter.get_info();
+        log::info!(
+            "Chosen adapter: {} ({:?}) using {} with driver {} {}",
+            adapter_info.name,
+            adapter_info.device_type,
+            adapter_info.backend,
+            adapter_info.driver,
+            adapter_info.driver_info
+        );
         let capabilities = match &internal {
-            GfxBacking::Surface(GfxSurface { surface, .. }) => surface.get_capabilities(adapter),
+            GfxBacking::Surface(GfxSurface { surface, .. }) => surface.get_capabilities(&adapter),
             GfxBacking::Buffer(_) => wgpu::SurfaceCapabilities::default(),
         };
         log::debug!("Found texture formats: {:?}", capabilities.formats);
-        let texture_format = capabilities
-            .formats
-            .into_iter()
-            .next()
-            .unwrap_or(TextureFormat::Rgba8UnormSrgb);
+        let texture_format = choose_format(&capabilities.formats, config.preferred_format);
         let alpha_mode = capabilities
             .alpha_modes
             .into_iter()
@@ -157,9 +353,12 @@ impl Gfx {
 
         let sample_flags = adapter.get_texture_format_features(texture_format).flags;
         log::debug!("Sample flags {sample_flags:#?}");
+        let multisample_count = supported_sample_count(sample_flags, config.multisample_count);
 
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // Copying from the surface is needed to capture it
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (capabilities.usages & wgpu::TextureUsages::COPY_SRC),
             format: texture_format,
             width: size.width,
             height: size.height,
@@ -168,34 +367,79 @@ impl Gfx {
             view_formats: vec![texture_format],
             desired_maximum_frame_latency: 2,
         };
-        if let GfxBacking::Surface(GfxSurface { surface, .. }) = &internal {
-            surface.configure(&device, &surface_config);
+        // A minimized window can have a zero size which can't be configured, wait for the next resize instead
+        let multisample_view = if is_zero_size(size) {
+            log::debug!("Skipping configure of zero sized surface");
+            None
+        } else {
+            if let GfxBacking::Surface(GfxSurface { surface, .. }) = &internal {
+                surface.configure(&device, &surface_config);
+            }
+            Self::create_multisample_view(
+                &device,
+                multisample_count,
+                &surface_config,
+                config.label_prefix,
+            )
         };
 
-        let multisample_view =
-            Self::create_multisample_view(&device, config.multisample_count, &surface_config);
-
         Self {
             backing: internal,
             device,
             queue,
             config: surface_config,
-            multisample_count: config.multisample_count,
+            multisample_count,
             multisample_view,
+            multisample_store: config.multisample_store,
+            label_prefix: config.label_prefix,
+            downlevel_flags: adapter.get_downlevel_capabilities().flags,
+            capture_alpha: config.capture_alpha,
+            buffer_clear_color: config.buffer_clear_color,
+            cursor_visible: AtomicBool::new(true),
+            cursor_grab_mode: Mutex::new(CursorGrabMode::None),
+            instance,
+            adapter,
+            adapter_info,
+            sample_flags,
+            mipmap_generator: OnceLock::new(),
         }
     }
 
+    /// The texture to render the next frame to.
+    ///
+    /// If the surface is lost or outdated, such as after a display change, it is reconfigured and acquired again once. Running
+    /// out of memory returns [`GfxError::OutOfMemory`], other surface errors such as a timeout can be skipped for a frame.
     pub fn get_current_texture(&self) -> Result<RenderableTexture, GfxError> {
         match &self.backing {
-            GfxBacking::Surface(GfxSurface { surface, .. }) => {
-                Ok(RenderableTexture::Surface(surface.get_current_texture()?))
-            }
+            GfxBacking::Surface(GfxSurface { surface, .. }) => Ok(RenderableTexture::Surface(
+                self.acquire_surface_texture(surface)?,
+            )),
             GfxBacking::Buffer(buffer) => {
                 Ok(RenderableTexture::Texture(Arc::clone(&buffer.texture)))
             }
         }
     }
 
+    fn acquire_surface_texture(
+        &self,
+        surface: &wgpu::Surface<'static>,
+    ) -> Result<wgpu::SurfaceTexture, GfxError> {
+        match surface.get_current_texture() {
+            Err(err @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
+                log::warn!("reconfiguring surface: {err}");
+                surface.configure(&self.device, &self.config);
+                surface.get_current_texture().map_err(surface_error)
+            }
+            result => result.map_err(surface_error),
+        }
+    }
+
+    /// The color attachment for a render pass drawing to `final_view`, a view of the texture from
+    /// [`Self::get_current_texture`].
+    ///
+    /// With multisampling the pass draws to the multisample texture and resolves into `final_view` at the end of the pass, so
+    /// buffer backings can be read back as usual. Render pipelines must use [`Self::multisample_count`] and the
+    /// [`wgpu::SurfaceConfiguration::format`] of [`Self::config`].
     pub fn color_attachments<'a>(
         &'a self,
         load: wgpu::LoadOp<wgpu::Color>,
@@ -207,7 +451,7 @@ impl Gfx {
                 resolve_target: Some(final_view),
                 ops: wgpu::Operations {
                     load,
-                    store: wgpu::StoreOp::Discard,
+                    store: self.multisample_store,
                 },
             })
         } else {
@@ -222,56 +466,157 @@ impl Gfx {
         }
     }
 
+    /// The color attachment for a render pass that starts by clearing `final_view` to `color`, see [`Self::color_attachments`]
+    pub fn clear_attachment<'a>(
+        &'a self,
+        color: wgpu::Color,
+        final_view: &'a wgpu::TextureView,
+    ) -> Result<wgpu::RenderPassColorAttachment<'a>, GfxError> {
+        self.color_attachments(wgpu::LoadOp::Clear(color), final_view)
+    }
+
+    /// The color attachment for a render pass that draws over the existing contents of `final_view`, see
+    /// [`Self::color_attachments`]
+    pub fn load_attachment<'a>(
+        &'a self,
+        final_view: &'a wgpu::TextureView,
+    ) -> Result<wgpu::RenderPassColorAttachment<'a>, GfxError> {
+        self.color_attachments(wgpu::LoadOp::Load, final_view)
+    }
+
+    /// Presents the current surface texture.
+    ///
+    /// Buffer backings have nothing to present so this does nothing, the texture is copied into the buffer when reading it
+    /// back with [`Self::read_pixels`] or [`Self::create_png`].
     pub fn present(&self) -> Result<(), GfxError> {
-        match &self.backing {
-            GfxBacking::Surface(GfxSurface { surface, .. }) => {
-                surface.get_current_texture()?.present();
-                Ok(())
-            }
-            GfxBacking::Buffer(buffer) => {
-                let mut encoder = self
-                    .device
-                    .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-                encoder.copy_texture_to_buffer(
-                    buffer.texture.as_image_copy(),
-                    wgpu::TexelCopyBufferInfo {
-                        buffer: &buffer.buffer,
-                        layout: wgpu::TexelCopyBufferLayout {
-                            offset: 0,
-                            bytes_per_row: Some(buffer.bytes_per_row),
-                            rows_per_image: None,
-                        },
-                    },
-                    buffer.extent,
-                );
-                self.queue.submit(Some(encoder.finish()));
-                Ok(())
-            }
+        if let GfxBacking::Surface(GfxSurface { surface, .. }) = &self.backing {
+            self.acquire_surface_texture(surface)?.present();
         }
+        Ok(())
+    }
+
+    /// Creates a command encoder with an optional label to identify it in graphics debuggers
+    pub fn create_encoder(&self, label: Option<&str>) -> wgpu::CommandEncoder {
+        self.device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label })
+    }
+
+    /// Submits command buffers to the queue.
+    ///
+    /// Command buffers are executed in the order they are given, after any previously submitted command buffers.
+    pub fn submit<I: IntoIterator<Item = wgpu::CommandBuffer>>(
+        &self,
+        command_buffers: I,
+    ) -> wgpu::SubmissionIndex {
+        self.queue.submit(command_buffers)
+    }
+
+    /// Blocks until all submitted GPU work has finished.
+    ///
+    /// Call this before dropping [`Gfx`] when shutting down so in flight work such as a final capture completes cleanly.
+    pub fn wait_idle(&self) {
+        self.submit([]);
+        self.device.poll(wgpu::Maintain::Wait);
+    }
+
+    /// Clears the buffer backing texture to a color. Does nothing for surface backings.
+    pub fn clear_buffer(&self, color: wgpu::Color) {
+        let GfxBacking::Buffer(buffer) = &self.backing else {
+            return;
+        };
+        let view = buffer
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self.create_encoder(label(self.label_prefix, "clear buffer").as_deref());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: label(self.label_prefix, "clear buffer pass").as_deref(),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        self.submit(Some(encoder.finish()));
     }
 
+    /// Resizes the surface or buffer backing and the multisample texture.
+    ///
+    /// Buffer backings are recreated at the new size and cleared to [`Self::buffer_clear_color`], so textures previously
+    /// returned by [`Self::get_current_texture`] or [`Self::buffer_texture`] are no longer read back.
     pub fn window_resize(&mut self, size: &PhysicalSize<u32>) {
         let old_size = (self.config.width, self.config.height);
+        if old_size == (size.width, size.height) {
+            // winit can emit duplicate resizes, reconfiguring is expensive so skip it
+            return;
+        }
+        if is_zero_size(*size) {
+            // Minimizing delivers a zero size which can't be configured, keep the old configuration until the next resize
+            log::trace!("ignoring zero window resize {old_size:?} -> {size:?}");
+            return;
+        }
         self.config.width = size.width;
         self.config.height = size.height;
         let new_size = (self.config.width, self.config.height);
         log::trace!("window resize {old_size:?} -> {new_size:?}");
-        if let GfxBacking::Surface(GfxSurface { surface, .. }) = &self.backing {
-            surface.configure(&self.device, &self.config);
-        };
+        match &mut self.backing {
+            GfxBacking::Surface(GfxSurface { surface, .. }) => {
+                surface.configure(&self.device, &self.config);
+            }
+            GfxBacking::Buffer(buffer) => {
+                *buffer = GfxBuffer::new(&self.device, *size, self.label_prefix);
+                self.clear_buffer(self.buffer_clear_color);
+            }
+        }
+        self.multisample_view = Self::create_multisample_view(
+            &self.device,
+            self.multisample_count,
+            &self.config,
+            self.label_prefix,
+        );
+    }
+
+    /// Changes the multisample count used by [`Self::color_attachments`], such as for an anti-aliasing setting, and returns
+    /// the count used.
+    ///
+    /// If the adapter doesn't support `count` for the texture format the highest supported count below it is used instead.
+    /// Render pipelines must be recreated with the new count in [`wgpu::MultisampleState::count`].
+    pub fn set_multisample_count(&mut self, count: NonZeroU32) -> NonZeroU32 {
+        let count = supported_sample_count(self.sample_flags, count);
+        if count == self.multisample_count {
+            return count;
+        }
+        self.multisample_count = count;
+        // A zero sized surface can't have a multisample texture, it is created on the next resize
         self.multisample_view =
-            Self::create_multisample_view(&self.device, self.multisample_count, &self.config);
+            if is_zero_size(PhysicalSize::new(self.config.width, self.config.height)) {
+                None
+            } else {
+                Self::create_multisample_view(
+                    &self.device,
+                    self.multisample_count,
+                    &self.config,
+                    self.label_prefix,
+                )
+            };
+        count
     }
 
     fn create_multisample_view(
         device: &wgpu::Device,
         multisample_count: NonZeroU32,
         config: &wgpu::SurfaceConfiguration,
+        label_prefix: Option<&str>,
     ) -> Option<wgpu::TextureView> {
         if multisample_count.get() > 1 {
             let view = device
                 .create_texture(&wgpu::TextureDescriptor {
-                    label: None,
+                    label: label(label_prefix, "multisample texture").as_deref(),
                     size: wgpu::Extent3d {
                         width: config.width,
                         height: config.height,
@@ -291,26 +636,124 @@ impl Gfx {
         }
     }
 
+    /// Grabs the cursor, locking it in place if the platform supports it otherwise confining it to the window. Use
+    /// [`Self::cursor_grab_mode`] to find out which was applied, or [`Self::set_cursor_grab_mode`] to choose one.
     pub fn set_cursor_grab(&self, grab: bool) -> Result<(), GfxError> {
         let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing else {
             return Ok(());
         };
-        if grab {
-            // Try locked then try confined
-            if let Err(err) = window.set_cursor_grab(winit::window::CursorGrabMode::Locked) {
-                log::error!("Failed to set cursor confined: {err}");
-                window.set_cursor_grab(winit::window::CursorGrabMode::Confined)?;
-            }
+        let mode = if grab {
+            grab_cursor(window)?
         } else {
-            window.set_cursor_grab(winit::window::CursorGrabMode::None)?;
-        }
+            window.set_cursor_grab(CursorGrabMode::None)?;
+            CursorGrabMode::None
+        };
+        self.store_cursor_grab_mode(mode);
         Ok(())
     }
 
+    /// Grabs the cursor with a specific mode without falling back to another.
+    ///
+    /// [`CursorGrabMode::Locked`] is not supported on Windows or X11 and [`CursorGrabMode::Confined`] is not supported on
+    /// macOS or the web, in which case an error is returned and the grab mode is unchanged. Does nothing for buffer backings.
+    pub fn set_cursor_grab_mode(&self, mode: CursorGrabMode) -> Result<(), GfxError> {
+        let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing else {
+            return Ok(());
+        };
+        window.set_cursor_grab(mode)?;
+        self.store_cursor_grab_mode(mode);
+        Ok(())
+    }
+
+    /// The grab mode last applied by [`Self::set_cursor_grab`], [`Self::set_cursor_grab_mode`] or
+    /// [`Self::set_relative_mouse`]. Always [`CursorGrabMode::None`] for buffer backings.
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        *self
+            .cursor_grab_mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn store_cursor_grab_mode(&self, mode: CursorGrabMode) {
+        *self
+            .cursor_grab_mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = mode;
+    }
+
     pub fn set_cursor_visible(&self, visible: bool) {
+        self.cursor_visible.store(visible, Ordering::Relaxed);
         if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
             window.set_cursor_visible(visible);
+        }
+    }
+
+    /// Enables or disables relative mouse mode for mouse look, which grabs and hides the cursor.
+    ///
+    /// Returns the grab mode that was applied. When it is [`CursorGrabMode::Confined`] the platform can't lock the cursor in
+    /// place, so re-center it each frame with [`Window::set_cursor_position`] to stop it reaching the edge of the window. Read the
+    /// motion with [`crate::input::raw::RawInputManagerState::mouse_motion`] as the cursor position won't change while locked.
+    /// Disabling restores the cursor visibility set with [`Self::set_cursor_visible`].
+    pub fn set_relative_mouse(&self, enabled: bool) -> Result<CursorGrabMode, GfxError> {
+        let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing else {
+            return Ok(CursorGrabMode::None);
         };
+        let mode = if enabled {
+            let mode = grab_cursor(window)?;
+            window.set_cursor_visible(false);
+            mode
+        } else {
+            window.set_cursor_grab(CursorGrabMode::None)?;
+            window.set_cursor_visible(self.cursor_visible.load(Ordering::Relaxed));
+            CursorGrabMode::None
+        };
+        self.store_cursor_grab_mode(mode);
+        Ok(mode)
+    }
+
+    /// Sets the title of the window. Does nothing for buffer backings.
+    pub fn set_title(&self, title: &str) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_title(title);
+        }
+    }
+
+    /// Sets the cursor icon shown over the window. Does nothing for buffer backings.
+    pub fn set_cursor_icon(&self, icon: CursorIcon) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_cursor(icon);
+        }
+    }
+
+    /// Allows the os input method editor (IME) to be used for text entry, such as for typing CJK text.
+    ///
+    /// IME events such as [`winit::event::WindowEvent::Ime`] are not delivered until this is enabled. Only enable it while a
+    /// text field is focused as some IMEs swallow key presses.
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_ime_allowed(allowed);
+        }
+    }
+
+    /// Sets the area of the text cursor in the window, so the IME candidate window can be placed next to it without covering it
+    pub fn set_ime_cursor_area(&self, position: PhysicalPosition<u32>, size: PhysicalSize<u32>) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_ime_cursor_area(position, size);
+        }
+    }
+
+    /// Prevents the window from being resized smaller than `size`, [`None`] removes the limit
+    pub fn set_min_inner_size(&self, size: Option<PhysicalSize<u32>>) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_min_inner_size(size);
+        }
+    }
+
+    /// Prevents the window from being resized larger than `size`, [`None`] removes the limit
+    pub fn set_max_inner_size(&self, size: Option<PhysicalSize<u32>>) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_max_inner_size(size);
+        }
     }
 
     pub fn toggle_fullscreen(&self) {
@@ -319,49 +762,285 @@ impl Gfx {
         }
     }
 
-    #[cfg(feature = "capture")]
-    pub fn create_png(&self, output: &std::path::Path) -> Result<(), GfxError> {
-        use std::{fs::File, io::Write as _};
+    /// Switches the window to exclusive fullscreen with a video mode from [`Self::video_modes`], changing the monitor's
+    /// resolution and refresh rate. [`None`] leaves fullscreen.
+    ///
+    /// Exclusive fullscreen is not supported on the web, iOS, Android or Wayland. Use [`Self::toggle_fullscreen`] for
+    /// borderless fullscreen which is supported everywhere.
+    pub fn set_fullscreen_exclusive(&self, mode: Option<VideoModeHandle>) {
+        if let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing {
+            window.set_fullscreen(mode.map(Fullscreen::Exclusive));
+        }
+    }
+
+    /// The video modes of the monitor the window is on, for [`Self::set_fullscreen_exclusive`]. Empty for buffer backings or
+    /// if the monitor can't be determined.
+    pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
+        self.window()
+            .and_then(Window::current_monitor)
+            .into_iter()
+            .flat_map(|monitor| monitor.video_modes())
+    }
 
+    /// Reads back the pixels of the buffer backing as tightly packed RGBA rows, with the row padding removed.
+    ///
+    /// The alpha is converted according to [`Self::capture_alpha`].
+    ///
+    /// Render commands must be submitted to the queue before calling this, the texture is then copied into the buffer and
+    /// this blocks until the copy has finished.
+    pub fn read_pixels(&self) -> Result<Vec<u8>, GfxError> {
         let GfxBacking::Buffer(GfxBuffer {
-            bytes_per_row,
-            buffer,
-            extent,
-            ..
+            buffer, texture, ..
         }) = &self.backing
         else {
-            return Err(GfxError::CannotCapturePngFromSurface);
+            return Err(GfxError::CannotReadPixelsFromSurface);
         };
-        let mut encoder = png::Encoder::new(File::create(output)?, extent.width, extent.height);
-        encoder.set_depth(png::BitDepth::Eight);
-        encoder.set_color(png::ColorType::Rgba);
-        let mut writer = encoder
-            .write_header()?
-            .into_stream_writer_with_size(extent.width as usize * 4)?;
-        let buffer_slice = buffer.slice(..);
+        self.readback(texture, buffer.clone())?.read(&self.device)
+    }
 
-        buffer_slice.map_async(wgpu::MapMode::Read, |_| {});
-        self.device.poll(wgpu::Maintain::Wait);
-        for chunk in buffer_slice
-            .get_mapped_range()
-            .chunks(*bytes_per_row as usize)
+    /// Fills every mip level after the first of `texture` by repeatedly downsampling the level above it, such as for a
+    /// texture rendered offscreen that is sampled by later passes.
+    ///
+    /// The texture must be 2d with the [`wgpu::TextureUsages::RENDER_ATTACHMENT`] and [`wgpu::TextureUsages::TEXTURE_BINDING`]
+    /// usages and a filterable float format, such as [`TextureFormat::Rgba8UnormSrgb`]. Create it with a `mip_level_count`
+    /// above 1, [`wgpu::Extent3d::max_mips`] gives the full chain. The buffer backing texture has a single level and can't be
+    /// sampled, copy it into a texture with mip levels first. Each layer of an array texture is downsampled separately.
+    ///
+    /// The work is submitted to the queue after any previously submitted commands, so submit the commands rendering the first
+    /// level before calling this.
+    pub fn generate_mipmaps(&self, texture: &wgpu::Texture) -> Result<(), GfxError> {
+        let generator = self
+            .mipmap_generator
+            .get_or_init(|| MipmapGenerator::new(&self.device, self.label_prefix));
+        let mut encoder = self.create_encoder(label(self.label_prefix, "mipmaps").as_deref());
+        generator.generate(&self.device, &mut encoder, texture, self.label_prefix)?;
+        self.submit(Some(encoder.finish()));
+        Ok(())
+    }
+
+    /// Reads the depth of a single pixel of a depth texture, such as the pixel under the cursor for mouse picking.
+    ///
+    /// The texture must have the [`wgpu::TextureUsages::COPY_SRC`] usage and be [`TextureFormat::Depth32Float`],
+    /// [`TextureFormat::Depth32FloatStencil8`] or [`TextureFormat::Depth16Unorm`]. The `Depth24Plus` formats have no defined
+    /// memory layout so they can't be read back, use `Depth32Float` for depth buffers you want to pick from.
+    ///
+    /// Some adapters such as WebGL can't copy depth textures at all, in which case
+    /// [`GfxError::DepthReadbackUnsupported`] is returned.
+    ///
+    /// Render commands must be submitted to the queue before calling this, it blocks until the copy has finished.
+    pub fn read_depth_at(&self, texture: &wgpu::Texture, x: u32, y: u32) -> Result<f32, GfxError> {
+        let format = texture.format();
+        let bytes_per_pixel = match format {
+            TextureFormat::Depth32Float | TextureFormat::Depth32FloatStencil8 => 4,
+            TextureFormat::Depth16Unorm => 2,
+            format => return Err(GfxError::UnsupportedDepthFormat(format)),
+        };
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(GfxError::TextureNotCopyable);
+        }
+        if !self
+            .downlevel_flags
+            .contains(wgpu::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES)
         {
-            writer.write_all(&chunk[..extent.width as usize * 4])?;
+            return Err(GfxError::DepthReadbackUnsupported);
         }
-        writer.finish()?;
-        Ok(())
+        if x >= texture.width() || y >= texture.height() {
+            return Err(GfxError::PixelOutOfBounds(x, y));
+        }
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: label(self.label_prefix, "depth readback buffer").as_deref(),
+            size: wgpu::COPY_BUFFER_ALIGNMENT,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder =
+            self.create_encoder(label(self.label_prefix, "depth readback").as_deref());
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::DepthOnly,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout::default(),
+            },
+            wgpu::Extent3d::default(),
+        );
+        self.submit(Some(encoder.finish()));
+
+        let buffer_slice = buffer.slice(..);
+        readback::map_blocking(&buffer_slice, &self.device)?;
+        let bytes = buffer_slice.get_mapped_range();
+        let depth = if bytes_per_pixel == 4 {
+            f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        } else {
+            f32::from(u16::from_le_bytes([bytes[0], bytes[1]])) / f32::from(u16::MAX)
+        };
+        drop(bytes);
+        buffer.unmap();
+        Ok(depth)
+    }
+
+    /// Copies the texture into the buffer and submits the copy, the buffer must be large enough to hold the padded rows
+    fn readback(
+        &self,
+        texture: &wgpu::Texture,
+        buffer: wgpu::Buffer,
+    ) -> Result<Readback, GfxError> {
+        let mut encoder = self.create_encoder(label(self.label_prefix, "readback").as_deref());
+        let readback = Readback::new(&mut encoder, texture, buffer, self.capture_alpha)?;
+        self.submit(Some(encoder.finish()));
+        Ok(readback)
+    }
+
+    /// Creates a buffer that a texture of this size can be copied into for reading back
+    fn create_readback_buffer(&self, size: wgpu::Extent3d) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: label(self.label_prefix, "readback buffer").as_deref(),
+            size: u64::from(readback::padded_bytes_per_row(size.width)) * u64::from(size.height),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Writes the pixels of the backing to a png, see [`Self::read_pixels`].
+    ///
+    /// For surface backings the current surface texture is acquired and copied, so this must be called while no texture
+    /// from [`Self::get_current_texture`] is held and the surface must support [`wgpu::TextureUsages::COPY_SRC`]. Use
+    /// [`Self::present_and_capture`] to capture a frame as it is rendered.
+    #[cfg(feature = "capture")]
+    pub fn create_png(&self, output: &std::path::Path) -> Result<(), GfxError> {
+        let (pixels, width, height) = self.capture_rgba()?;
+        write_png(output, width, height, &pixels)
+    }
+
+    /// Reads back the pixels of the backing as tightly packed RGBA rows along with the width and height, such as for
+    /// thumbnails or passing to an image library.
+    ///
+    /// This is the same capture as [`Self::create_png`] without encoding it, so the same restrictions on surface backings
+    /// apply. It blocks until the copy has finished.
+    pub fn capture_rgba(&self) -> Result<(Vec<u8>, u32, u32), GfxError> {
+        let readback = self.capture_readback()?;
+        let pixels = readback.read(&self.device)?;
+        Ok((pixels, readback.width(), readback.height()))
+    }
+
+    /// Writes the pixels of the backing to a png without blocking the thread while waiting for the copy, see
+    /// [`Self::create_png`].
+    ///
+    /// The copy is submitted immediately and the returned future resolves once it has been mapped, which only happens when
+    /// the device is polled. Keep rendering frames or call [`wgpu::Device::poll`] with [`wgpu::Maintain::Poll`] every
+    /// update while waiting. On the web this happens automatically.
+    #[cfg(feature = "capture")]
+    pub fn create_png_async(
+        &self,
+        output: &std::path::Path,
+    ) -> impl std::future::Future<Output = Result<(), GfxError>> {
+        let readback = self.capture_readback();
+        let output = output.to_path_buf();
+        async move {
+            let readback = readback?;
+            let pixels = readback.read_async().await?;
+            write_png(&output, readback.width(), readback.height(), &pixels)
+        }
+    }
+
+    /// Copies the buffer texture or the current surface texture for reading back
+    fn capture_readback(&self) -> Result<Readback, GfxError> {
+        match &self.backing {
+            GfxBacking::Buffer(GfxBuffer {
+                buffer, texture, ..
+            }) => self.readback(texture, buffer.clone()),
+            GfxBacking::Surface(GfxSurface { surface, .. }) => {
+                let texture = self.acquire_surface_texture(surface)?;
+                let buffer = self.create_readback_buffer(texture.texture.size());
+                // Dropping the surface texture without presenting discards it
+                self.readback(&texture.texture, buffer)
+            }
+        }
+    }
+
+    /// Presents a rendered texture and writes exactly what was presented to a png.
+    ///
+    /// The texture from [`Self::get_current_texture`] is only acquired once, it is copied for reading back before being
+    /// presented so the capture can't be a frame behind what was shown. Surface textures can only be captured if the surface
+    /// supports [`wgpu::TextureUsages::COPY_SRC`].
+    #[cfg(feature = "capture")]
+    pub fn present_and_capture(
+        &self,
+        texture: RenderableTexture,
+        output: &std::path::Path,
+    ) -> Result<(), GfxError> {
+        let buffer = match &self.backing {
+            GfxBacking::Buffer(GfxBuffer { buffer, .. }) => buffer.clone(),
+            GfxBacking::Surface(_) => self.create_readback_buffer(texture.texture().size()),
+        };
+        let readback = self.readback(texture.texture(), buffer)?;
+        texture.present();
+        let pixels = readback.read(&self.device)?;
+        write_png(output, readback.width(), readback.height(), &pixels)
+    }
+
+    /// Information about the chosen adapter such as the GPU name and driver, useful for bug reports.
+    ///
+    /// Software adapters have a [`wgpu::AdapterInfo::device_type`] of [`wgpu::DeviceType::Cpu`].
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// The wgpu instance, such as for creating surfaces for extra windows or sharing it with another library
+    pub fn instance(&self) -> &wgpu::Instance {
+        &self.instance
+    }
+
+    /// The adapter the [`Self::device`] was requested from, such as for querying its features and limits or creating
+    /// another device on the same GPU
+    pub fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    /// The maximum width and height of 2D textures that can be created on the device
+    pub fn max_texture_dimension(&self) -> u32 {
+        self.device.limits().max_texture_dimension_2d
     }
 
     pub fn aspect_ratio(&self) -> f32 {
         self.config.width as f32 / self.config.height as f32
     }
 
+    /// Converts a pixel coordinate into normalized device coordinates using the size of the backing, see [`pixel_to_ndc`]
+    pub fn pixel_to_ndc(&self, pixel: [f64; 2]) -> [f32; 2] {
+        pixel_to_ndc(
+            pixel,
+            PhysicalSize::new(self.config.width, self.config.height),
+        )
+    }
+
     pub fn window(&self) -> Option<&Window> {
         let GfxBacking::Surface(GfxSurface { window, .. }) = &self.backing else {
             return None;
         };
         Some(window)
     }
+
+    /// The wgpu surface if this is backed by a window
+    pub fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        let GfxBacking::Surface(GfxSurface { surface, .. }) = &self.backing else {
+            return None;
+        };
+        Some(surface)
+    }
+
+    /// The texture rendered to if this is backed by a buffer
+    pub fn buffer_texture(&self) -> Option<&Arc<wgpu::Texture>> {
+        let GfxBacking::Buffer(GfxBuffer { texture, .. }) = &self.backing else {
+            return None;
+        };
+        Some(texture)
+    }
 }
 
 pub enum GfxBacking {
@@ -369,6 +1048,110 @@ pub enum GfxBacking {
     Buffer(GfxBuffer),
 }
 
+#[cfg(feature = "capture")]
+fn write_png(
+    output: &std::path::Path,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<(), GfxError> {
+    let mut encoder = png::Encoder::new(std::fs::File::create(output)?, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.write_header()?.write_image_data(pixels)?;
+    Ok(())
+}
+
+/// Picks the preferred format if supported, otherwise the first sRGB format so colors don't depend on the driver's order
+fn choose_format(formats: &[TextureFormat], preferred: Option<TextureFormat>) -> TextureFormat {
+    if let Some(preferred) = preferred {
+        if formats.contains(&preferred) {
+            return preferred;
+        }
+        if !formats.is_empty() {
+            log::warn!("Preferred texture format {preferred:?} is not supported");
+        }
+    }
+    formats
+        .iter()
+        .copied()
+        .find(TextureFormat::is_srgb)
+        .or_else(|| formats.first().copied())
+        .unwrap_or(TextureFormat::Rgba8UnormSrgb)
+}
+
+/// Converts a pixel coordinate into normalized device coordinates, with y up and both axes in the range -1 to 1.
+///
+/// This is the inverse of the viewport transform. Pixel coordinates are continuous like cursor positions, so (0, 0) is the
+/// top left corner of the top left pixel. Add 0.5 to integer pixel indices to get the center of the pixel.
+/// Returns the center of the screen if the size is zero.
+///
+/// # Example
+/// ```
+/// use rugfx::gfx::pixel_to_ndc;
+/// use winit::dpi::PhysicalSize;
+///
+/// let size = PhysicalSize::new(200, 100);
+/// assert_eq!(pixel_to_ndc([0.0, 0.0], size), [-1.0, 1.0]);
+/// assert_eq!(pixel_to_ndc([100.0, 50.0], size), [0.0, 0.0]);
+/// assert_eq!(pixel_to_ndc([200.0, 100.0], size), [1.0, -1.0]);
+/// assert_eq!(pixel_to_ndc([10.0, 10.0], PhysicalSize::new(0, 0)), [0.0, 0.0]);
+/// ```
+#[expect(clippy::cast_possible_truncation)]
+pub fn pixel_to_ndc(pixel: [f64; 2], size: PhysicalSize<u32>) -> [f32; 2] {
+    if is_zero_size(size) {
+        return [0.0, 0.0];
+    }
+    let x = pixel[0] / f64::from(size.width) * 2.0 - 1.0;
+    let y = 1.0 - pixel[1] / f64::from(size.height) * 2.0;
+    [x as f32, y as f32]
+}
+
+/// Maps `OutOfMemory` to [`GfxError::OutOfMemory`], passing other surface errors through
+fn surface_error(err: SurfaceError) -> GfxError {
+    match err {
+        SurfaceError::OutOfMemory => GfxError::OutOfMemory,
+        err => GfxError::SurfaceError(err),
+    }
+}
+
+/// Prefixes a resource label, returning [`None`] without a prefix
+fn label(prefix: Option<&str>, name: &str) -> Option<String> {
+    let prefix = prefix?;
+    Some(format!("{prefix} {name}"))
+}
+
+fn is_zero_size(size: PhysicalSize<u32>) -> bool {
+    size.width == 0 || size.height == 0
+}
+
+/// Grabs the cursor, locking it in place if the platform supports it otherwise confining it to the window
+fn grab_cursor(window: &Window) -> Result<CursorGrabMode, GfxError> {
+    if let Err(err) = window.set_cursor_grab(CursorGrabMode::Locked) {
+        log::error!("Failed to set cursor locked, falling back to confined: {err}");
+        window.set_cursor_grab(CursorGrabMode::Confined)?;
+        return Ok(CursorGrabMode::Confined);
+    }
+    Ok(CursorGrabMode::Locked)
+}
+
+/// The highest sample count supported by the format that is at most `requested`
+fn supported_sample_count(
+    flags: wgpu::TextureFormatFeatureFlags,
+    requested: NonZeroU32,
+) -> NonZeroU32 {
+    let supported = [16, 8, 4, 2]
+        .into_iter()
+        .filter(|&count| count <= requested.get())
+        .find(|&count| flags.sample_count_supported(count))
+        .and_then(NonZeroU32::new)
+        .unwrap_or(NonZeroU32::MIN);
+    if supported != requested {
+        log::warn!("Multisample count {requested} is not supported, using {supported} instead");
+    }
+    supported
+}
+
 fn fullscreen_mode(fullscreen: bool) -> Option<Fullscreen> {
     if fullscreen {
         Some(Fullscreen::Borderless(None))
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use wgpu::Device;
 use winit::dpi::PhysicalSize;
 
+use super::{label, readback::padded_bytes_per_row};
+
 pub struct GfxBuffer {
     pub bytes_per_row: u32,
     pub buffer: wgpu::Buffer,
@@ -11,11 +13,10 @@ pub struct GfxBuffer {
 }
 
 impl GfxBuffer {
-    pub fn new(device: &Device, size: PhysicalSize<u32>) -> Self {
-        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
-        let bytes_per_row = 4 * size.width + (align - (4 * size.width) % align) % align;
+    pub fn new(device: &Device, size: PhysicalSize<u32>, label_prefix: Option<&str>) -> Self {
+        let bytes_per_row = padded_bytes_per_row(size.width);
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
+            label: label(label_prefix, "buffer").as_deref(),
             size: u64::from(bytes_per_row * size.height),
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
@@ -32,7 +33,7 @@ impl GfxBuffer {
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8UnormSrgb,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
-            label: None,
+            label: label(label_prefix, "buffer texture").as_deref(),
             view_formats: &[],
         });
 
@@ -0,0 +1,179 @@
+#[cfg(feature = "capture")]
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use super::{CaptureAlpha, GfxError};
+
+/// Rows copied from a texture into a buffer must be aligned to [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`]
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    4 * width + (align - (4 * width) % align) % align
+}
+
+/// Maps a buffer for reading and blocks until it is mapped, returning an error instead of panicking if mapping failed, such as
+/// when the device was lost
+pub fn map_blocking(slice: &wgpu::BufferSlice<'_>, device: &wgpu::Device) -> Result<(), GfxError> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        // The receiver is only dropped once polling has returned
+        _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    // The callback has run once the poll returns, unless the device was lost or polling can't block such as on the web
+    receiver.try_recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+    Ok(())
+}
+
+/// A pending copy of a texture into a buffer that can be mapped to read the pixels
+pub struct Readback {
+    buffer: wgpu::Buffer,
+    bytes_per_row: u32,
+    extent: wgpu::Extent3d,
+    swizzle: bool,
+    alpha: CaptureAlpha,
+}
+
+impl Readback {
+    /// Records a copy of `texture` into `buffer` which must be at least [`padded_bytes_per_row`] times the height in size
+    pub fn new(
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        buffer: wgpu::Buffer,
+        alpha: CaptureAlpha,
+    ) -> Result<Self, GfxError> {
+        let swizzle = match texture.format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            format => return Err(GfxError::UnsupportedCaptureFormat(format)),
+        };
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(GfxError::TextureNotCopyable);
+        }
+        let extent = texture.size();
+        let bytes_per_row = padded_bytes_per_row(extent.width);
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+        Ok(Self {
+            buffer,
+            bytes_per_row,
+            extent,
+            swizzle,
+            alpha,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.extent.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.extent.height
+    }
+
+    /// Waits for the copy to finish and returns the pixels as tightly packed RGBA rows
+    pub fn read(&self, device: &wgpu::Device) -> Result<Vec<u8>, GfxError> {
+        map_blocking(&self.buffer.slice(..), device)?;
+        Ok(self.mapped_pixels())
+    }
+
+    /// Returns the pixels as tightly packed RGBA rows once the copy has finished, without blocking the thread.
+    ///
+    /// The buffer is only mapped when the device is polled, which happens when submitting to the queue or calling
+    /// [`wgpu::Device::poll`]. On the web this happens automatically.
+    #[cfg(feature = "capture")]
+    pub async fn read_async(&self) -> Result<Vec<u8>, GfxError> {
+        let state = Arc::new(Mutex::new(MapState::default()));
+        let callback_state = Arc::clone(&state);
+        self.buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let mut state = callback_state
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        MapFuture { state }.await?;
+        Ok(self.mapped_pixels())
+    }
+
+    /// Copies the pixels out of the mapped buffer and unmaps it
+    fn mapped_pixels(&self) -> Vec<u8> {
+        let row_len = self.extent.width as usize * 4;
+        let buffer_slice = self.buffer.slice(..);
+        let mut pixels: Vec<u8> = buffer_slice
+            .get_mapped_range()
+            .chunks(self.bytes_per_row as usize)
+            .flat_map(|chunk| &chunk[..row_len])
+            .copied()
+            .collect();
+        self.buffer.unmap();
+        if self.swizzle {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        if self.alpha == CaptureAlpha::Unpremultiply {
+            for pixel in pixels.chunks_exact_mut(4) {
+                unpremultiply(pixel);
+            }
+        }
+        pixels
+    }
+}
+
+#[cfg(feature = "capture")]
+#[derive(Default)]
+struct MapState {
+    result: Option<Result<(), wgpu::BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+/// Resolves when the [`wgpu::BufferSlice::map_async`] callback has been called
+#[cfg(feature = "capture")]
+struct MapFuture {
+    state: Arc<Mutex<MapState>>,
+}
+
+#[cfg(feature = "capture")]
+impl Future for MapFuture {
+    type Output = Result<(), wgpu::BufferAsyncError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(result) = state.result.take() {
+            Poll::Ready(result)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Converts a premultiplied RGBA pixel to straight alpha, fully transparent pixels are left as is
+fn unpremultiply(pixel: &mut [u8]) {
+    let alpha = u16::from(pixel[3]);
+    if alpha == 0 {
+        return;
+    }
+    for channel in &mut pixel[..3] {
+        let straight = (u16::from(*channel) * 255 + alpha / 2) / alpha;
+        *channel = u8::try_from(straight).unwrap_or(u8::MAX);
+    }
+}
@@ -1,13 +1,25 @@
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
 use winit::{
-    event::MouseButton,
-    keyboard::{KeyCode, PhysicalKey},
+    event::{KeyEvent, MouseButton},
+    keyboard::{Key, KeyCode, NativeKeyCode, PhysicalKey},
 };
 
 pub mod bindings;
+#[cfg(feature = "egui")]
+pub mod egui;
+pub mod frame_pacer;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 pub mod input_manager;
-pub mod inputs;
+mod key_code;
 pub mod raw;
+pub mod rebind;
+pub mod replay;
+pub mod snapshot;
 pub mod ticker;
+pub mod touch;
 
 /// Input represents any kind of user input
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -16,6 +28,34 @@ pub enum Input {
     Key(PhysicalKey),
     /// Mouse button
     Mouse(winit::event::MouseButton),
+    /// The lowercase character a key types on the user's keyboard layout, see [`Input::logical`].
+    ///
+    /// Use this for shortcuts that should follow the layout, such as Q to quit, and [`Input::Key`] for controls that depend
+    /// on the key position such as WASD. Keys that don't type a character, such as enter or the arrow keys, are in the same
+    /// place on every layout so bind them with [`Input::Key`].
+    Logical(char),
+    /// A mouse wheel tick, pressed on the update a whole line is scrolled and released on the next
+    Scroll(ScrollDirection),
+    /// Gamepad button, pressed on any connected gamepad
+    #[cfg(feature = "gamepad")]
+    Gamepad(gamepad::GamepadButton),
+}
+
+/// The direction of an [`Input::Scroll`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ScrollDirection {
+    /// Scrolling the wheel away from the user
+    Up,
+    /// Scrolling the wheel towards the user
+    Down,
+    Left,
+    Right,
+}
+
+impl From<ScrollDirection> for Input {
+    fn from(value: ScrollDirection) -> Self {
+        Self::Scroll(value)
+    }
 }
 
 impl From<PhysicalKey> for Input {
@@ -30,8 +70,371 @@ impl From<KeyCode> for Input {
     }
 }
 
+impl Input {
+    /// The logical key that types `character`, lowercased so it matches whether or not shift or caps lock is held
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::Input;
+    ///
+    /// assert_eq!(Input::logical('Q'), Input::Logical('q'));
+    /// assert_eq!(Input::logical('q').to_string(), "Logical:q");
+    /// ```
+    pub fn logical(character: char) -> Self {
+        Self::Logical(lowercase(character))
+    }
+
+    /// The kind of device this input comes from
+    pub fn device(&self) -> InputDevice {
+        match self {
+            Self::Key(_) | Self::Logical(_) => InputDevice::Keyboard,
+            Self::Mouse(_) | Self::Scroll(_) => InputDevice::Mouse,
+            #[cfg(feature = "gamepad")]
+            Self::Gamepad(_) => InputDevice::Gamepad,
+        }
+    }
+
+    /// A stable identifier for the input that can be used to look up an icon, such as `"kbd_w"` or `"mouse_left"`.
+    ///
+    /// Keyboard ids are prefixed with `kbd_`, mouse ids with `mouse_` and gamepad ids with `pad_`. Keys without a known id return
+    /// `"kbd_unknown"` and other mouse buttons return `"mouse_other"`.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::Input;
+    /// use winit::{event::MouseButton, keyboard::KeyCode};
+    ///
+    /// assert_eq!(Input::from(KeyCode::KeyW).glyph_id(), "kbd_w");
+    /// assert_eq!(Input::from(KeyCode::ArrowUp).glyph_id(), "kbd_arrow_up");
+    /// assert_eq!(Input::from(MouseButton::Left).glyph_id(), "mouse_left");
+    /// ```
+    pub fn glyph_id(&self) -> &'static str {
+        match self {
+            Self::Key(PhysicalKey::Code(code)) => key_code::glyph_id(*code),
+            Self::Key(PhysicalKey::Unidentified(_)) => key_code::UNKNOWN_KEY_GLYPH_ID,
+            Self::Mouse(MouseButton::Left) => "mouse_left",
+            Self::Mouse(MouseButton::Right) => "mouse_right",
+            Self::Mouse(MouseButton::Middle) => "mouse_middle",
+            Self::Mouse(MouseButton::Back) => "mouse_back",
+            Self::Mouse(MouseButton::Forward) => "mouse_forward",
+            Self::Mouse(MouseButton::Other(_)) => "mouse_other",
+            Self::Scroll(ScrollDirection::Up) => "mouse_scroll_up",
+            Self::Scroll(ScrollDirection::Down) => "mouse_scroll_down",
+            Self::Scroll(ScrollDirection::Left) => "mouse_scroll_left",
+            Self::Scroll(ScrollDirection::Right) => "mouse_scroll_right",
+            Self::Logical(character) => logical_glyph_id(*character),
+            #[cfg(feature = "gamepad")]
+            Self::Gamepad(button) => gamepad::button_glyph_id(*button),
+        }
+    }
+}
+
+/// Formats the input as `Device:Name`, such as `Key:KeyW` or `Mouse:Left`, which can be parsed back with [`FromStr`].
+///
+/// Key names are the winit [`KeyCode`] variant names, logical keys are the character they type and gamepad buttons, such as
+/// `Gamepad:South`, are the gilrs button names. Keys winit can't identify are written as their platform scancode in hex, such
+/// as `Key:Scan(0x26)`, which is parsed back as the scancode for the current platform so it only round trips on the same
+/// platform.
+///
+/// # Example
+/// ```
+/// use rugfx::input::{Input, ScrollDirection};
+/// use winit::{event::MouseButton, keyboard::KeyCode};
+///
+/// assert_eq!(Input::from(KeyCode::KeyW).to_string(), "Key:KeyW");
+/// assert_eq!("Mouse:Left".parse(), Ok(Input::from(MouseButton::Left)));
+/// assert_eq!("Logical:q".parse(), Ok(Input::logical('q')));
+/// assert_eq!(Input::logical('+').to_string(), "Logical:U+002B");
+/// assert_eq!("Logical:U+002B".parse(), Ok(Input::logical('+')));
+/// assert_eq!("Mouse:Other(7)".parse(), Ok(Input::from(MouseButton::Other(7))));
+/// assert_eq!("Scroll:Up".parse(), Ok(Input::Scroll(ScrollDirection::Up)));
+///
+/// let scancode: Input = "Key:Scan(0x26)".parse().unwrap();
+/// assert_eq!(scancode.to_string(), "Key:Scan(0x26)");
+/// ```
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key(PhysicalKey::Code(code)) => match key_code::name(*code) {
+                Some(name) => write!(f, "Key:{name}"),
+                None => write!(f, "Key:{code:?}"),
+            },
+            Self::Key(PhysicalKey::Unidentified(native)) => match native {
+                NativeKeyCode::Android(code) | NativeKeyCode::Xkb(code) => {
+                    write!(f, "Key:Scan({code:#x})")
+                }
+                NativeKeyCode::MacOS(code) | NativeKeyCode::Windows(code) => {
+                    write!(f, "Key:Scan({code:#x})")
+                }
+                NativeKeyCode::Unidentified => write!(f, "Key:Unidentified"),
+            },
+            Self::Mouse(MouseButton::Other(id)) => write!(f, "Mouse:Other({id})"),
+            Self::Mouse(button) => write!(f, "Mouse:{button:?}"),
+            Self::Logical(character) if is_plain_logical(*character) => {
+                write!(f, "Logical:{character}")
+            }
+            Self::Logical(character) => write!(f, "Logical:U+{:04X}", u32::from(*character)),
+            Self::Scroll(direction) => write!(f, "Scroll:{direction:?}"),
+            #[cfg(feature = "gamepad")]
+            Self::Gamepad(button) => write!(f, "Gamepad:{}", gamepad::button_name(*button)),
+        }
+    }
+}
+
+/// Serializes as the string from [`Display`] so saved bindings are readable and don't depend on winit's internal layout.
+///
+/// # Example
+/// ```
+/// use rugfx::input::{bindings::Bindings, Input};
+/// use winit::{event::MouseButton, keyboard::KeyCode};
+///
+/// let json = serde_json::to_string(&Input::from(KeyCode::KeyW)).unwrap();
+/// assert_eq!(json, r#""Key:KeyW""#);
+/// assert_eq!(serde_json::from_str::<Input>(&json).unwrap(), KeyCode::KeyW.into());
+///
+/// #[derive(Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+/// enum Binds {
+///     Jump,
+///     Fire,
+/// }
+///
+/// let mut bindings = Bindings::default();
+/// bindings.bind(KeyCode::Space.into(), Binds::Jump);
+/// bindings.bind(MouseButton::Left.into(), Binds::Fire);
+/// bindings.bind(KeyCode::KeyF.into(), Binds::Fire);
+/// let json = serde_json::to_string(&bindings).unwrap();
+/// let loaded: Bindings<Binds> = serde_json::from_str(&json).unwrap();
+/// for bind in [Binds::Jump, Binds::Fire] {
+///     assert_eq!(loaded.transform(&bind), bindings.transform(&bind));
+/// }
+/// ```
+#[cfg(feature = "serde")]
+impl serde::Serialize for Input {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Input {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error returned when parsing an [`Input`] from a string fails
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseInputError {
+    #[error("missing device in `{0}`, expected `<Device>:<name>` such as `Key:KeyW`")]
+    MissingDevice(String),
+    #[error("unknown device `{0}`")]
+    UnknownDevice(String),
+    #[error("unknown key `{0}`")]
+    UnknownKey(String),
+    #[error("unknown mouse button `{0}`")]
+    UnknownMouseButton(String),
+    #[error("unknown scroll direction `{0}`")]
+    UnknownScrollDirection(String),
+    #[cfg(feature = "gamepad")]
+    #[error("unknown gamepad button `{0}`")]
+    UnknownGamepadButton(String),
+}
+
+impl FromStr for Input {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some((device, name)) = s.split_once(':') else {
+            return Err(ParseInputError::MissingDevice(s.to_owned()));
+        };
+        let name = name.trim();
+        match device.trim() {
+            "Key" => parse_key(name),
+            "Mouse" => parse_mouse_button(name),
+            "Logical" => parse_logical(name),
+            "Scroll" => parse_scroll_direction(name),
+            #[cfg(feature = "gamepad")]
+            "Gamepad" => gamepad::button_from_name(name)
+                .map(Input::Gamepad)
+                .ok_or_else(|| ParseInputError::UnknownGamepadButton(name.to_owned())),
+            device => Err(ParseInputError::UnknownDevice(device.to_owned())),
+        }
+    }
+}
+
+fn parse_key(name: &str) -> Result<Input, ParseInputError> {
+    if name == "Unidentified" {
+        return Ok(Input::Key(PhysicalKey::Unidentified(
+            NativeKeyCode::Unidentified,
+        )));
+    }
+    if let Some(code) = name
+        .strip_prefix("Scan(0x")
+        .and_then(|code| code.strip_suffix(')'))
+    {
+        return u32::from_str_radix(code, 16)
+            .ok()
+            .and_then(native_scancode)
+            .map(|native| Input::Key(PhysicalKey::Unidentified(native)))
+            .ok_or_else(|| ParseInputError::UnknownKey(name.to_owned()));
+    }
+    key_code::from_name(name)
+        .map(Input::from)
+        .ok_or_else(|| ParseInputError::UnknownKey(name.to_owned()))
+}
+
+/// The native key code for a scancode on the current platform
+fn native_scancode(code: u32) -> Option<NativeKeyCode> {
+    if cfg!(target_os = "windows") {
+        u16::try_from(code).ok().map(NativeKeyCode::Windows)
+    } else if cfg!(target_os = "macos") {
+        u16::try_from(code).ok().map(NativeKeyCode::MacOS)
+    } else if cfg!(target_os = "android") {
+        Some(NativeKeyCode::Android(code))
+    } else {
+        Some(NativeKeyCode::Xkb(code))
+    }
+}
+
+fn parse_logical(name: &str) -> Result<Input, ParseInputError> {
+    let mut chars = name.chars();
+    let character = match (chars.next(), chars.next()) {
+        (Some(character), None) => Some(character),
+        _ => name
+            .strip_prefix("U+")
+            .and_then(|code| u32::from_str_radix(code, 16).ok())
+            .and_then(char::from_u32),
+    };
+    character
+        .map(Input::logical)
+        .ok_or_else(|| ParseInputError::UnknownKey(name.to_owned()))
+}
+
+/// Characters that are written as is, others are written as their code point so they don't clash with the separators of
+/// [`crate::input::bindings::Bindings::export_text`] or get trimmed
+fn is_plain_logical(character: char) -> bool {
+    character.is_alphanumeric()
+        || (character.is_ascii_punctuation() && !matches!(character, ',' | '+' | '=' | '#'))
+}
+
+fn lowercase(character: char) -> char {
+    let mut lower = character.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        // Characters that lowercase to several characters are left as is
+        _ => character,
+    }
+}
+
+/// Letters and digits use the glyph of the key that types them on a US layout
+fn logical_glyph_id(character: char) -> &'static str {
+    let name = match character {
+        'a'..='z' => format!("Key{}", character.to_ascii_uppercase()),
+        '0'..='9' => format!("Digit{character}"),
+        _ => return key_code::UNKNOWN_KEY_GLYPH_ID,
+    };
+    key_code::from_name(&name).map_or(key_code::UNKNOWN_KEY_GLYPH_ID, key_code::glyph_id)
+}
+
+fn parse_mouse_button(name: &str) -> Result<Input, ParseInputError> {
+    let button = match name {
+        "Left" => MouseButton::Left,
+        "Right" => MouseButton::Right,
+        "Middle" => MouseButton::Middle,
+        "Back" => MouseButton::Back,
+        "Forward" => MouseButton::Forward,
+        _ => name
+            .strip_prefix("Other(")
+            .and_then(|id| id.strip_suffix(')'))
+            .and_then(|id| id.parse().ok())
+            .map(MouseButton::Other)
+            .ok_or_else(|| ParseInputError::UnknownMouseButton(name.to_owned()))?,
+    };
+    Ok(Input::Mouse(button))
+}
+
+fn parse_scroll_direction(name: &str) -> Result<Input, ParseInputError> {
+    let direction = match name {
+        "Up" => ScrollDirection::Up,
+        "Down" => ScrollDirection::Down,
+        "Left" => ScrollDirection::Left,
+        "Right" => ScrollDirection::Right,
+        _ => return Err(ParseInputError::UnknownScrollDirection(name.to_owned())),
+    };
+    Ok(Input::Scroll(direction))
+}
+
+/// The kind of device an [`Input`] comes from, useful for showing the right button prompts
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum InputDevice {
+    Keyboard,
+    Mouse,
+    #[cfg(feature = "gamepad")]
+    Gamepad,
+}
+
+/// Uses the [`KeyEvent::physical_key`] so the input matches the key position regardless of keyboard layout.
+///
+/// Use [`Input::from_key_event_logical`] for the character the key types on the user's layout instead.
+impl From<&KeyEvent> for Input {
+    fn from(value: &KeyEvent) -> Self {
+        Self::Key(value.physical_key)
+    }
+}
+
+impl Input {
+    /// The [`Input::Logical`] for the character the key of `event` types, or [`None`] if it doesn't type a single character.
+    ///
+    /// Unlike the [`From`] implementation, which uses the physical key, this follows the user's keyboard layout, see
+    /// [`Input::from_logical_key`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::Input;
+    /// use winit::event::KeyEvent;
+    ///
+    /// fn bind_typed_key(event: &KeyEvent) -> Input {
+    ///     Input::from_key_event_logical(event).unwrap_or_else(|| Input::from(event))
+    /// }
+    /// ```
+    pub fn from_key_event_logical(event: &KeyEvent) -> Option<Self> {
+        Self::from_logical_key(&event.logical_key)
+    }
+
+    /// The [`Input::Logical`] for a logical key that types a single character, lowercased like [`Input::logical`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::Input;
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Input::from_logical_key(&Key::Character("Q".into())), Some(Input::logical('q')));
+    /// assert_eq!(Input::from_logical_key(&Key::Character("ab".into())), None);
+    /// assert_eq!(Input::from_logical_key(&Key::Named(NamedKey::Enter)), None);
+    /// ```
+    pub fn from_logical_key(key: &Key) -> Option<Self> {
+        let Key::Character(text) = key else {
+            return None;
+        };
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(character), None) => Some(Self::logical(character)),
+            _ => None,
+        }
+    }
+}
+
 impl From<MouseButton> for Input {
     fn from(value: MouseButton) -> Self {
         Self::Mouse(value)
     }
 }
+
+#[cfg(feature = "gamepad")]
+impl From<gamepad::GamepadButton> for Input {
+    fn from(value: gamepad::GamepadButton) -> Self {
+        Self::Gamepad(value)
+    }
+}
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::hash::Hash;
+use std::str::FromStr;
 
-use super::Input;
+use thiserror::Error;
+
+use super::{Input, ParseInputError};
 
 /// A trait alias for what your [`InputBind`] must implement.
 ///
@@ -21,15 +25,35 @@ pub trait InputBind: PartialEq + Eq + Hash + 'static {}
 impl<B> InputBind for B where B: PartialEq + Eq + Hash + 'static {}
 
 /// A map of keys to their bindings.
+///
+/// A binding can have any number of single keys bound with [`Self::bind`], any of which trigger it, and any number of chords
+/// bound with [`Self::bind_chord`], which trigger it when every key in the chord is held.
+///
+/// With the `serde` feature bindings can be saved and loaded, each key is stored as a string such as `"Key:KeyW"`, see
+/// [`Input`]'s `Serialize` implementation.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "B: serde::Serialize",
+        deserialize = "B: serde::de::DeserializeOwned"
+    ))
+)]
 pub struct Bindings<B: InputBind> {
     key_map: HashMap<B, Vec<Input>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    chord_map: HashMap<B, Vec<Vec<Input>>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    analog_sources: HashMap<B, Vec<(AxisSource, f32)>>,
 }
 
 impl<B: InputBind> Default for Bindings<B> {
     fn default() -> Self {
         Self {
             key_map: HashMap::default(),
+            chord_map: HashMap::default(),
+            analog_sources: HashMap::default(),
         }
     }
 }
@@ -49,20 +73,318 @@ impl<B: InputBind> Bindings<B> {
         self.key_map.entry(input).or_default().retain(|k| k != key);
     }
 
+    /// Bind a chord of keys that must all be held to a binding, such as control and S for save.
+    ///
+    /// The binding is pressed when every key in the chord is held and at least one of them was pressed since the last update, so
+    /// the keys can be pressed in any order. Chords are independent of the single keys bound with [`Self::bind`], if S is bound
+    /// to one binding and control and S to another then pressing control and S triggers both. Check the chord binding first
+    /// and skip the other if it was pressed when that matters.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::bindings::Bindings;
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Save,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind_chord(vec![KeyCode::ControlLeft.into(), KeyCode::KeyS.into()], Binds::Save);
+    /// assert!(bindings.transform(&Binds::Save).is_empty());
+    /// assert_eq!(bindings.chords(&Binds::Save).len(), 1);
+    /// ```
+    pub fn bind_chord(&mut self, keys: Vec<Input>, input: B) {
+        let chord_list = self.chord_map.entry(input).or_default();
+        if keys.is_empty() || chord_list.contains(&keys) {
+            return;
+        }
+        chord_list.push(keys);
+    }
+
+    /// Unbind a chord and binding pair
+    pub fn unbind_chord(&mut self, keys: &[Input], input: B) {
+        self.chord_map
+            .entry(input)
+            .or_default()
+            .retain(|c| c != keys);
+    }
+
+    /// Bind a key to a binding, removing it from every other binding it was bound to.
+    ///
+    /// Returns the bindings the key was taken from.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::bindings::Bindings;
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Jump,
+    ///     Interact,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind(KeyCode::Space.into(), Binds::Jump);
+    ///
+    /// let taken_from = bindings.reassign(KeyCode::Space.into(), Binds::Interact);
+    /// assert_eq!(taken_from, vec![Binds::Jump]);
+    /// assert!(bindings.transform(&Binds::Jump).is_empty());
+    /// assert_eq!(bindings.transform(&Binds::Interact), &[KeyCode::Space.into()]);
+    /// ```
+    pub fn reassign(&mut self, key: Input, to: B) -> Vec<B>
+    where
+        B: Clone,
+    {
+        let mut taken_from = Vec::new();
+        for (input, list) in &mut self.key_map {
+            if *input != to && list.contains(&key) {
+                list.retain(|k| *k != key);
+                taken_from.push(input.clone());
+            }
+        }
+        self.bind(key, to);
+        taken_from
+    }
+
     /// Transform an input into a list of its bound keys
     pub fn transform(&self, input: &B) -> &[Input] {
         self.key_map.get(input).map_or(&[], Vec::as_slice)
     }
 
+    /// Bind an analog source to a binding so [`crate::input::input_manager::InputManagerState::axis`] returns its value.
+    ///
+    /// The source value is multiplied by `scale` and only the positive part is used, so bind a stick to the positive binding of
+    /// an [`AxisBind`] with a scale of 1.0 and to the negative binding with a scale of -1.0. Analog sources only affect the
+    /// axis methods, they never make a binding pressed or held.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::bindings::{AxisSource, Bindings};
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     LookRight,
+    ///     LookLeft,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind(KeyCode::ArrowRight.into(), Binds::LookRight);
+    /// bindings.bind_analog(AxisSource::MouseMotionX, 0.1, Binds::LookRight);
+    /// bindings.bind(KeyCode::ArrowLeft.into(), Binds::LookLeft);
+    /// bindings.bind_analog(AxisSource::MouseMotionX, -0.1, Binds::LookLeft);
+    /// ```
+    pub fn bind_analog(&mut self, source: AxisSource, scale: f32, input: B) {
+        let analog_list = self.analog_sources.entry(input).or_default();
+        if analog_list.contains(&(source, scale)) {
+            return;
+        }
+        analog_list.push((source, scale));
+    }
+
+    /// Unbind an analog source and binding pair
+    pub fn unbind_analog(&mut self, source: AxisSource, input: B) {
+        self.analog_sources
+            .entry(input)
+            .or_default()
+            .retain(|(s, _)| *s != source);
+    }
+
+    /// The analog sources bound to a binding and their scale, see [`Self::bind_analog`]
+    pub fn analog(&self, input: &B) -> &[(AxisSource, f32)] {
+        self.analog_sources.get(input).map_or(&[], Vec::as_slice)
+    }
+
+    /// The chords bound to a binding, see [`Self::bind_chord`]
+    pub fn chords(&self, input: &B) -> &[Vec<Input>] {
+        self.chord_map.get(input).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over every binding that has at least one key or chord bound, use [`Self::transform`] and [`Self::chords`] to get
+    /// the keys for each binding.
+    pub fn binds(&self) -> impl Iterator<Item = &B> {
+        let keys = self
+            .key_map
+            .iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(bind, _)| bind);
+        let chord_only = self
+            .chord_map
+            .iter()
+            .filter(|(bind, chords)| !chords.is_empty() && self.transform(bind).is_empty())
+            .map(|(bind, _)| bind);
+        keys.chain(chord_only)
+    }
+
+    /// Write the bindings in a hand editable text format with one `action = Key1, Key2` line per binding.
+    ///
+    /// Chords are written as their keys joined by `+`, such as `save = Key:ControlLeft+Key:KeyS`. Analog bindings are not
+    /// written. Lines are sorted by action so the output is stable. See [`Self::import_text`] to read it back.
+    pub fn export_text(&self) -> String
+    where
+        B: Display,
+    {
+        let mut lines: Vec<String> = self
+            .binds()
+            .map(|bind| {
+                let keys = self.transform(bind).iter().map(Input::to_string);
+                let chords = self.chords(bind).iter().map(|chord| {
+                    let keys: Vec<String> = chord.iter().map(Input::to_string).collect();
+                    keys.join("+")
+                });
+                let keys: Vec<String> = keys.chain(chords).collect();
+                format!("{bind} = {}", keys.join(", "))
+            })
+            .collect();
+        lines.sort();
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+
+    /// Read bindings written by [`Self::export_text`] and bind them in addition to the existing bindings.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. If any line is malformed nothing is bound and the error
+    /// reports the line number.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::bindings::Bindings;
+    /// use winit::{event::MouseButton, keyboard::KeyCode};
+    ///
+    /// let mut bindings: Bindings<String> = Bindings::default();
+    /// bindings
+    ///     .import_text("# movement\njump = Key:Space, Mouse:Right\n")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     bindings.transform(&"jump".to_owned()),
+    ///     &[KeyCode::Space.into(), MouseButton::Right.into()]
+    /// );
+    /// assert_eq!(bindings.export_text(), "jump = Key:Space, Mouse:Right\n");
+    ///
+    /// let err = bindings.import_text("jump = Key:Space\nfire Key:KeyF").unwrap_err();
+    /// assert_eq!(err.line, 2);
+    ///
+    /// bindings.import_text("save = Key:ControlLeft+Key:KeyS").unwrap();
+    /// assert_eq!(bindings.chords(&"save".to_owned()).len(), 1);
+    ///
+    /// // Keys winit can't identify are bound by their scancode
+    /// bindings.import_text("crouch = Key:Scan(0x5e)").unwrap();
+    /// assert_eq!(bindings.transform(&"crouch".to_owned())[0].to_string(), "Key:Scan(0x5e)");
+    /// ```
+    pub fn import_text(&mut self, text: &str) -> Result<(), ImportTextError>
+    where
+        B: FromStr,
+    {
+        let mut parsed_keys = Vec::new();
+        let mut parsed_chords = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let error = |kind| ImportTextError { line: i + 1, kind };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (bind, keys) = line
+                .split_once('=')
+                .ok_or_else(|| error(ImportTextErrorKind::MissingEquals))?;
+            let bind = bind.trim();
+            // Parsed for each map it is stored in so bindings don't need to be Clone
+            let parse_bind = || {
+                bind.parse::<B>()
+                    .map_err(|_| error(ImportTextErrorKind::InvalidBind(bind.to_owned())))
+            };
+            let mut singles = Vec::new();
+            let mut chords = Vec::new();
+            for key in keys.split(',') {
+                let mut chord = key
+                    .split('+')
+                    .map(str::parse::<Input>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| error(ImportTextErrorKind::InvalidInput(err)))?;
+                if chord.len() == 1 {
+                    singles.append(&mut chord);
+                } else {
+                    chords.push(chord);
+                }
+            }
+            parsed_keys.push((parse_bind()?, singles));
+            if !chords.is_empty() {
+                parsed_chords.push((parse_bind()?, chords));
+            }
+        }
+        for (bind, keys) in parsed_keys {
+            let key_list = self.key_map.entry(bind).or_default();
+            for key in keys {
+                if !key_list.contains(&key) {
+                    key_list.push(key);
+                }
+            }
+        }
+        for (bind, chords) in parsed_chords {
+            let chord_list = self.chord_map.entry(bind).or_default();
+            for chord in chords {
+                if !chord_list.contains(&chord) {
+                    chord_list.push(chord);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Merges two sets of key bindings together, in a non-destructive way
     pub fn merge(&mut self, other: Self) {
         for (input, list) in other.key_map {
             let v = self.key_map.entry(input).or_default();
             v.extend(list);
         }
+        for (input, list) in other.chord_map {
+            let v = self.chord_map.entry(input).or_default();
+            v.extend(list);
+        }
+        for (input, list) in other.analog_sources {
+            let v = self.analog_sources.entry(input).or_default();
+            v.extend(list);
+        }
     }
 }
 
+/// Error returned by [`Bindings::import_text`] for a malformed line
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("line {line}: {kind}")]
+pub struct ImportTextError {
+    /// The 1 based line number of the malformed line
+    pub line: usize,
+    pub kind: ImportTextErrorKind,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ImportTextErrorKind {
+    #[error("expected `action = Key1, Key2`")]
+    MissingEquals,
+    #[error("invalid binding `{0}`")]
+    InvalidBind(String),
+    #[error("{0}")]
+    InvalidInput(#[from] ParseInputError),
+}
+
+/// An analog input that can be bound with [`Bindings::bind_analog`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisSource {
+    /// Horizontal mouse motion since the last update in pixels, multiplied by the mouse sensitivity
+    MouseMotionX,
+    /// Vertical mouse motion since the last update in pixels, multiplied by the mouse sensitivity
+    MouseMotionY,
+    /// Horizontal mouse wheel scroll since the last update in lines
+    MouseWheelX,
+    /// Vertical mouse wheel scroll since the last update in lines
+    MouseWheelY,
+    /// A gamepad axis such as a stick, from -1.0 to 1.0
+    #[cfg(feature = "gamepad")]
+    Gamepad(super::gamepad::GamepadAxis),
+}
+
 /// An axis binding that combines two [`Bindings`] two form a 1 dimensional axis
 ///
 /// Use [`crate::input::input_manager::InputManagerState::axis`] to get a value from your axis bind or one of the multi dimension methods:
@@ -0,0 +1,168 @@
+use winit::{
+    dpi::PhysicalSize,
+    event::MouseButton,
+    keyboard::{KeyCode, PhysicalKey},
+};
+
+use super::{raw::RawInputManagerState, Input};
+
+/// Converts the tracked input state into [`egui::RawInput`] and remembers whether egui wants the input.
+///
+/// Each frame build the input with [`Self::raw_input`], run the egui context, then call [`Self::update`] so the game can check
+/// [`Self::wants_pointer_input`] and [`Self::wants_keyboard_input`] to ignore input that egui consumed.
+#[derive(Debug, Default)]
+pub struct EguiInput {
+    wants_pointer_input: bool,
+    wants_keyboard_input: bool,
+}
+
+impl EguiInput {
+    /// Build the egui input for this frame from the input state and the physical size of the window
+    pub fn raw_input(
+        &self,
+        input: &RawInputManagerState,
+        screen_size: PhysicalSize<u32>,
+        pixels_per_point: f32,
+    ) -> egui::RawInput {
+        let modifiers = modifiers(input);
+        let [x, y] = input.mouse_position();
+        #[expect(clippy::cast_possible_truncation)]
+        let pos = egui::pos2(x as f32, y as f32) / pixels_per_point;
+
+        let mut events = vec![egui::Event::PointerMoved(pos)];
+        let edges = input
+            .pressed_inputs()
+            .map(|i| (i, true))
+            .chain(input.released_inputs().map(|i| (i, false)));
+        for (i, pressed) in edges {
+            match i {
+                Input::Mouse(button) => {
+                    if let Some(button) = pointer_button(*button) {
+                        events.push(egui::Event::PointerButton {
+                            pos,
+                            button,
+                            pressed,
+                            modifiers,
+                        });
+                    }
+                }
+                Input::Key(PhysicalKey::Code(code)) => {
+                    if let Some(key) = key(*code) {
+                        events.push(egui::Event::Key {
+                            key,
+                            physical_key: Some(key),
+                            pressed,
+                            repeat: false,
+                            modifiers,
+                        });
+                    }
+                }
+                // Keys are sent to egui by their physical key
+                Input::Key(PhysicalKey::Unidentified(_)) | Input::Logical(_) => (),
+                // Scrolling is sent below as the wheel delta
+                Input::Scroll(_) => (),
+                #[cfg(feature = "gamepad")]
+                Input::Gamepad(_) => (),
+            }
+        }
+        if !input.text_input().is_empty() {
+            events.push(egui::Event::Text(input.text_input().to_owned()));
+        }
+        let [wheel_x, wheel_y] = input.mouse_wheel_delta();
+        if wheel_x != 0.0 || wheel_y != 0.0 {
+            events.push(egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Line,
+                delta: egui::vec2(wheel_x, wheel_y),
+                modifiers,
+            });
+        }
+        let [pixel_x, pixel_y] = input.mouse_wheel_pixel_delta();
+        if pixel_x != 0.0 || pixel_y != 0.0 {
+            #[expect(clippy::cast_possible_truncation)]
+            events.push(egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta: egui::vec2(pixel_x as f32, pixel_y as f32) / pixels_per_point,
+                modifiers,
+            });
+        }
+
+        let mut raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(screen_size.width as f32, screen_size.height as f32) / pixels_per_point,
+            )),
+            time: Some(input.game_time().as_secs_f64()),
+            predicted_dt: input.delta_time_f32(),
+            modifiers,
+            events,
+            ..Default::default()
+        };
+        raw_input
+            .viewports
+            .entry(raw_input.viewport_id)
+            .or_default()
+            .native_pixels_per_point = Some(pixels_per_point);
+        raw_input
+    }
+
+    /// Call this after running the egui context to record whether egui wants the input
+    pub fn update(&mut self, ctx: &egui::Context) {
+        self.wants_pointer_input = ctx.wants_pointer_input();
+        self.wants_keyboard_input = ctx.wants_keyboard_input();
+    }
+
+    /// Returns true if egui is using the mouse, for example when hovering a window, so the game should ignore it
+    pub fn wants_pointer_input(&self) -> bool {
+        self.wants_pointer_input
+    }
+
+    /// Returns true if egui is using the keyboard, for example when typing in a text field, so the game should ignore it
+    pub fn wants_keyboard_input(&self) -> bool {
+        self.wants_keyboard_input
+    }
+}
+
+fn modifiers(input: &RawInputManagerState) -> egui::Modifiers {
+    let held =
+        |left: KeyCode, right: KeyCode| input.held(&left.into()) || input.held(&right.into());
+    let ctrl = held(KeyCode::ControlLeft, KeyCode::ControlRight);
+    let mac_cmd = cfg!(target_os = "macos") && held(KeyCode::SuperLeft, KeyCode::SuperRight);
+    egui::Modifiers {
+        alt: held(KeyCode::AltLeft, KeyCode::AltRight),
+        ctrl,
+        shift: held(KeyCode::ShiftLeft, KeyCode::ShiftRight),
+        mac_cmd,
+        command: if cfg!(target_os = "macos") {
+            mac_cmd
+        } else {
+            ctrl
+        },
+    }
+}
+
+fn pointer_button(button: MouseButton) -> Option<egui::PointerButton> {
+    match button {
+        MouseButton::Left => Some(egui::PointerButton::Primary),
+        MouseButton::Right => Some(egui::PointerButton::Secondary),
+        MouseButton::Middle => Some(egui::PointerButton::Middle),
+        MouseButton::Back => Some(egui::PointerButton::Extra1),
+        MouseButton::Forward => Some(egui::PointerButton::Extra2),
+        MouseButton::Other(_) => None,
+    }
+}
+
+fn key(code: KeyCode) -> Option<egui::Key> {
+    match code {
+        KeyCode::BracketLeft => Some(egui::Key::OpenBracket),
+        KeyCode::BracketRight => Some(egui::Key::CloseBracket),
+        KeyCode::NumpadEnter => Some(egui::Key::Enter),
+        KeyCode::NumpadAdd => Some(egui::Key::Plus),
+        KeyCode::NumpadSubtract => Some(egui::Key::Minus),
+        KeyCode::NumpadDecimal => Some(egui::Key::Period),
+        _ => {
+            // egui understands most winit key code names, letters are named without the `Key` prefix
+            let name = format!("{code:?}");
+            egui::Key::from_name(name.strip_prefix("Key").unwrap_or(&name))
+        }
+    }
+}
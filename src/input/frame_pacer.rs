@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+/// Limits the frame rate by waiting at the end of each frame until the target frame time has passed.
+///
+/// Only needed with [`winit::event_loop::ControlFlow::Poll`] which otherwise renders as fast as possible. With
+/// [`winit::event_loop::ControlFlow::Wait`] the event loop already sleeps until there is something to do and
+/// [`winit::event_loop::ControlFlow::WaitUntil`] can be used to wake at a fixed rate.
+///
+/// Waiting sleeps for most of the remaining time and then spins for the last [`Self::spin_threshold`], as the os may oversleep
+/// by a millisecond or more. [`Self::wait`] is not available on wasm where the browser controls the frame rate.
+///
+/// # Example
+/// ```no_run
+/// use rugfx::input::frame_pacer::FramePacer;
+///
+/// let mut pacer = FramePacer::from_fps(60.0);
+/// loop {
+///     // Update and draw the frame
+///     pacer.wait();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FramePacer {
+    /// The minimum time between frames, zero disables the limit
+    pub target_frame_time: Duration,
+    /// How much of the wait is spent spinning instead of sleeping. Defaults to 1ms.
+    ///
+    /// Larger values are more accurate but use more cpu.
+    pub spin_threshold: Duration,
+    last_frame: Instant,
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        Self {
+            target_frame_time: Duration::ZERO,
+            spin_threshold: Duration::from_millis(1),
+            last_frame: Instant::now(),
+        }
+    }
+}
+
+impl FramePacer {
+    /// Initialize a new frame pacer with a target time between frames
+    pub fn new(target_frame_time: Duration) -> Self {
+        Self {
+            target_frame_time,
+            ..Default::default()
+        }
+    }
+
+    /// Initialize a new frame pacer with a target frame rate, a frame rate that isn't positive disables the limit
+    pub fn from_fps(fps: f64) -> Self {
+        let mut pacer = Self::default();
+        pacer.set_target_fps(fps);
+        pacer
+    }
+
+    /// Set the target frame rate, a frame rate that isn't positive disables the limit
+    pub fn set_target_fps(&mut self, fps: f64) {
+        self.target_frame_time = Duration::try_from_secs_f64(fps.recip()).unwrap_or_default();
+    }
+
+    /// How long [`Self::wait`] would wait if called now
+    pub fn remaining(&self) -> Duration {
+        (self.last_frame + self.target_frame_time).saturating_duration_since(Instant::now())
+    }
+
+    /// Call this at the end of every frame to wait until the target frame time has passed since the previous frame.
+    ///
+    /// If the frame took longer than the target it returns immediately without trying to catch up.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn wait(&mut self) {
+        let deadline = self.last_frame + self.target_frame_time;
+        let now = Instant::now();
+        if deadline <= now {
+            self.last_frame = now;
+            return;
+        }
+        let sleep = deadline
+            .saturating_duration_since(now)
+            .saturating_sub(self.spin_threshold);
+        if !sleep.is_zero() {
+            std::thread::sleep(sleep);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+        // Measure from the deadline rather than now so the frame rate doesn't drift
+        self.last_frame = deadline;
+    }
+}
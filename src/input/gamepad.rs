@@ -0,0 +1,163 @@
+use std::{collections::HashMap, time::Duration};
+
+use gilrs::{
+    ff::{self, BaseEffect, BaseEffectType, EffectBuilder, Repeat, Replay, Ticks},
+    Gilrs,
+};
+
+pub use gilrs::{Axis as GamepadAxis, Button as GamepadButton, GamepadId};
+
+macro_rules! buttons {
+    ($($button:ident => $glyph:literal,)*) => {
+        /// The name of the button as written in the gilrs source, such as `"South"`
+        pub fn button_name(button: GamepadButton) -> &'static str {
+            match button {
+                $(GamepadButton::$button => stringify!($button),)*
+            }
+        }
+
+        /// The inverse of [`button_name`]
+        pub fn button_from_name(name: &str) -> Option<GamepadButton> {
+            match name {
+                $(stringify!($button) => Some(GamepadButton::$button),)*
+                _ => None,
+            }
+        }
+
+        /// Glyph id of the button, see [`crate::input::Input::glyph_id`]
+        pub fn button_glyph_id(button: GamepadButton) -> &'static str {
+            match button {
+                $(GamepadButton::$button => $glyph,)*
+            }
+        }
+    };
+}
+
+buttons! {
+    South => "pad_south",
+    East => "pad_east",
+    North => "pad_north",
+    West => "pad_west",
+    C => "pad_c",
+    Z => "pad_z",
+    LeftTrigger => "pad_left_trigger",
+    LeftTrigger2 => "pad_left_trigger2",
+    RightTrigger => "pad_right_trigger",
+    RightTrigger2 => "pad_right_trigger2",
+    Select => "pad_select",
+    Start => "pad_start",
+    Mode => "pad_mode",
+    LeftThumb => "pad_left_thumb",
+    RightThumb => "pad_right_thumb",
+    DPadUp => "pad_dpad_up",
+    DPadDown => "pad_dpad_down",
+    DPadLeft => "pad_dpad_left",
+    DPadRight => "pad_dpad_right",
+    Unknown => "pad_unknown",
+}
+
+/// Owns the gilrs gamepad backend and any force feedback effects that are playing.
+pub struct Gamepads {
+    gilrs: Gilrs,
+    rumbles: HashMap<GamepadId, ff::Effect>,
+}
+
+impl std::fmt::Debug for Gamepads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gamepads")
+            .field("gilrs", &self.gilrs)
+            .field("rumbling", &self.rumbles.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Gamepads {
+    /// Initialize the gamepad backend.
+    ///
+    /// On platforms gilrs doesn't support this succeeds but no gamepads are ever connected.
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) | Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+            Err(err) => return Err(Box::new(err)),
+        };
+        Ok(Self {
+            gilrs,
+            rumbles: HashMap::default(),
+        })
+    }
+
+    /// The gilrs backend, for anything not wrapped here such as gamepad names and power info
+    pub fn gilrs(&self) -> &Gilrs {
+        &self.gilrs
+    }
+
+    /// The gilrs backend, mutable to allow polling events or building custom force feedback effects
+    pub fn gilrs_mut(&mut self) -> &mut Gilrs {
+        &mut self.gilrs
+    }
+
+    /// Take the next gamepad event from the backend, [`crate::input::raw::RawInputManagerState::preupdate`] does this every
+    /// update so only use this if you are handling the events yourself
+    pub fn next_event(&mut self) -> Option<gilrs::Event> {
+        self.gilrs.next_event()
+    }
+
+    /// Rumble a gamepad for `duration` with the `strong` and `weak` motors at a magnitude from 0.0 to 1.0.
+    ///
+    /// The strong motor is the low frequency motor and the weak motor is the high frequency motor. Replaces any rumble
+    /// already playing on the gamepad. Does nothing if the gamepad is disconnected or doesn't support force feedback.
+    pub fn rumble(
+        &mut self,
+        gamepad: GamepadId,
+        strong: f32,
+        weak: f32,
+        duration: Duration,
+    ) -> Result<(), ff::Error> {
+        let supported = self
+            .gilrs
+            .connected_gamepad(gamepad)
+            .is_some_and(|g| g.is_ff_supported());
+        if !supported {
+            self.rumbles.remove(&gamepad);
+            return Ok(());
+        }
+        let ticks = Ticks::from_ms(u32::try_from(duration.as_millis()).unwrap_or(u32::MAX));
+        let scheduling = Replay {
+            play_for: ticks,
+            ..Default::default()
+        };
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(strong),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(weak),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .repeat(Repeat::For(ticks))
+            .gamepads(&[gamepad])
+            .finish(&mut self.gilrs)?;
+        effect.play()?;
+        // Dropping the previous effect stops it
+        self.rumbles.insert(gamepad, effect);
+        Ok(())
+    }
+
+    /// Stop any rumble playing on a gamepad
+    pub fn stop_rumble(&mut self, gamepad: GamepadId) {
+        self.rumbles.remove(&gamepad);
+    }
+}
+
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn magnitude(value: f32) -> u16 {
+    // NaN saturates to zero
+    (value.clamp(0.0, 1.0) * f32::from(u16::MAX)) as u16
+}
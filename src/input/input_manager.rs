@@ -1,8 +1,12 @@
-use std::time::Duration;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
+};
 
 use super::{
-    bindings::{AxisBind, Bindings, InputBind},
+    bindings::{AxisBind, AxisSource, Bindings, InputBind},
     raw::RawInputManagerState,
+    Input, InputDevice,
 };
 
 #[derive(Debug)]
@@ -11,15 +15,58 @@ pub struct InputManagerState<B: InputBind> {
     pub mouse_sensitivity: [f64; 2],
     /// Input bindings
     pub bindings: Bindings<B>,
-    /// The current time elapsed since the start of the event loop scaled by the `time_scale`.
-    pub time: Duration,
+    /// The scaled time accumulated since the start of the event loop, see [`Self::game_time`]
+    time: Duration,
     /// The time scale controls how fast time runs. A value of 1.0 is normal. A value of < 1.0 is slower than normal and > 1.0 is faster than normal.
     pub time_scale: f32,
     /// The ema alpha used to smooth the frame rate that is returned by [`Self::smooth_frame_rate`]. Defaults to 0.05
     pub smooth_frame_rate_alpha: f32,
     /// The ema smoothed frame rate
     pub smooth_frame_rate: f32,
+    /// How many recent frame times are kept for [`Self::frame_times`] and the frame time statistics. Defaults to 0 which
+    /// disables them.
+    pub frame_time_capacity: usize,
+    /// The most recent unscaled frame times, oldest first
+    frame_times: VecDeque<Duration>,
     pub raw: RawInputManagerState,
+    needs_redraw: bool,
+    /// The wall-clock game time of the update each held binding was pressed in, used by [`Self::pressed_repeating`]
+    repeat_starts: HashMap<B, Duration>,
+    /// The game time each interval passed to [`Self::tick_every`] last fired
+    interval_timers: HashMap<Duration, Duration>,
+    /// When false all binding queries report nothing, see [`Self::set_enabled`]
+    enabled: bool,
+    /// Inputs that were held when input was re-enabled and are ignored until released
+    suppressed: HashSet<Input>,
+    /// Gamepad axis values closer to zero than this are ignored, see [`Self::set_dead_zone`]
+    dead_zone: f32,
+    /// How gamepad axis values are shaped after the dead zone, see [`ResponseCurve`]
+    pub response_curve: ResponseCurve,
+}
+
+/// How an analog axis value outside of the dead zone maps to the value returned by the axis methods.
+///
+/// Curves other than linear give finer control near the center of a stick while still reaching 1.0 at the edge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCurve {
+    /// The value is used as is
+    #[default]
+    Linear,
+    /// The value is squared, keeping its sign
+    Quadratic,
+    /// The value is cubed
+    Cubic,
+}
+
+impl ResponseCurve {
+    /// Apply the curve to a value from -1.0 to 1.0
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            Self::Linear => value,
+            Self::Quadratic => value * value.abs(),
+            Self::Cubic => value * value * value,
+        }
+    }
 }
 
 impl<B: InputBind> Default for InputManagerState<B> {
@@ -31,45 +78,458 @@ impl<B: InputBind> Default for InputManagerState<B> {
             time_scale: 1.0,
             smooth_frame_rate_alpha: 0.05,
             smooth_frame_rate: 0.0,
+            frame_time_capacity: 0,
+            frame_times: VecDeque::new(),
             raw: RawInputManagerState::default(),
+            needs_redraw: true,
+            repeat_starts: HashMap::default(),
+            interval_timers: HashMap::default(),
+            enabled: true,
+            suppressed: HashSet::default(),
+            dead_zone: 0.0,
+            response_curve: ResponseCurve::Linear,
         }
     }
 }
 
 impl<B: InputBind> InputManagerState<B> {
+    /// Initialize a new input manager with bindings
+    pub fn new(bindings: Bindings<B>) -> Self {
+        Self {
+            bindings,
+            ..Default::default()
+        }
+    }
+
+    /// Set the mouse sensitivity in the x and y direction
+    #[must_use]
+    pub fn with_mouse_sensitivity(self, mouse_sensitivity: [f64; 2]) -> Self {
+        Self {
+            mouse_sensitivity,
+            ..self
+        }
+    }
+
     pub fn preupdate(&mut self) {
         self.raw.preupdate();
         self.time += self.delta_time();
         self.smooth_frame_rate = self.smooth_frame_rate_alpha * self.raw.frame_rate()
             + (1.0 - self.smooth_frame_rate_alpha) * self.smooth_frame_rate;
+        self.record_frame_time();
+        self.needs_redraw |= self.raw.needs_redraw();
+        let raw = &self.raw;
+        self.suppressed.retain(|k| raw.held(k) || raw.released(k));
     }
 
-    /// Returns true if the binding was pressed since the last update
-    pub fn pressed(&self, input: &B) -> bool {
-        self.bindings
-            .transform(input)
+    /// Enables or disables all input, for example during cutscenes or loading screens.
+    ///
+    /// While disabled [`Self::pressed`], [`Self::held`], [`Self::released`], the axes and [`Self::mouse_motion`] report nothing
+    /// but the raw input state is still tracked. When re-enabled any inputs that are still held are ignored until they are
+    /// released, so presses that happened while disabled are never reported.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::{dry_binds, input::input_manager::InputManagerState};
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Fire,
+    /// }
+    ///
+    /// let mouse_event = |state| WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state,
+    ///     button: MouseButton::Left,
+    /// };
+    /// let mut input = InputManagerState::new(dry_binds! { MouseButton::Left => Binds::Fire });
+    ///
+    /// input.set_enabled(false);
+    /// input.raw.process_window_event(mouse_event(ElementState::Pressed));
+    /// assert!(!input.pressed(&Binds::Fire));
+    ///
+    /// // Still held from while input was disabled
+    /// input.set_enabled(true);
+    /// assert!(!input.pressed(&Binds::Fire));
+    /// assert!(!input.held(&Binds::Fire));
+    ///
+    /// input.raw.process_window_event(mouse_event(ElementState::Released));
+    /// assert!(!input.released(&Binds::Fire));
+    /// input.raw.clear();
+    /// input.preupdate();
+    ///
+    /// input.raw.process_window_event(mouse_event(ElementState::Pressed));
+    /// assert!(input.pressed(&Binds::Fire));
+    /// ```
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled && !self.enabled {
+            self.suppressed = self.raw.held_inputs().copied().collect();
+        }
+        self.enabled = enabled;
+    }
+
+    /// Returns false if input has been disabled with [`Self::set_enabled`]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Filters out bound inputs that should not be reported because input is disabled or they are suppressed
+    fn active_inputs(&self, input: &B) -> impl Iterator<Item = &Input> {
+        let inputs = if self.enabled {
+            self.bindings.transform(input)
+        } else {
+            &[]
+        };
+        inputs.iter().filter(|k| !self.suppressed.contains(k))
+    }
+
+    /// Like [`Self::active_inputs`] but for the chords bound to the binding, a chord is inactive if any of its keys are suppressed
+    fn active_chords(&self, input: &B) -> impl Iterator<Item = &[Input]> {
+        let chords = if self.enabled {
+            self.bindings.chords(input)
+        } else {
+            &[]
+        };
+        chords
             .iter()
-            .any(|k| self.raw.pressed(k))
+            .filter(|chord| !chord.iter().any(|k| self.suppressed.contains(k)))
+            .map(Vec::as_slice)
+    }
+
+    /// Returns true if the frame should be redrawn because of input, a resize or a call to [`Self::request_redraw`].
+    ///
+    /// This stays true until [`Self::frame_drawn`] is called. When using [`winit::event_loop::ControlFlow::Wait`] check this
+    /// each update and call [`winit::window::Window::request_redraw`] if it is true, then draw on
+    /// [`winit::event::WindowEvent::RedrawRequested`] so the app only renders when something changed.
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw
+    }
+
+    /// Marks the frame as needing to be redrawn, for example when an animation is running
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    /// Call this after drawing a frame to clear [`Self::needs_redraw`]
+    pub fn frame_drawn(&mut self) {
+        self.needs_redraw = false;
+    }
+
+    /// Resets the accumulated [`Self::game_time`] and the [`Self::smooth_frame_rate`] to zero and forgets the [`Self::frame_times`].
+    ///
+    /// Bindings, mouse sensitivity and the time scale are left untouched.
+    pub fn reset_time(&mut self) {
+        self.time = Duration::ZERO;
+        self.smooth_frame_rate = 0.0;
+        self.frame_times.clear();
+        self.interval_timers.clear();
+    }
+
+    fn record_frame_time(&mut self) {
+        while self.frame_times.len() >= self.frame_time_capacity {
+            if self.frame_times.pop_front().is_none() {
+                return;
+            }
+        }
+        self.frame_times.push_back(self.raw.delta_time());
+    }
+
+    /// The most recent unscaled frame times, oldest first, useful for drawing a frame time graph.
+    ///
+    /// Only the last [`Self::frame_time_capacity`] frames are kept, which is 0 by default so nothing is recorded.
+    pub fn frame_times(&self) -> impl ExactSizeIterator<Item = Duration> + '_ {
+        self.frame_times.iter().copied()
+    }
+
+    /// The shortest of the recent [`Self::frame_times`], None if none have been recorded
+    pub fn frame_time_min(&self) -> Option<Duration> {
+        self.frame_times().min()
+    }
+
+    /// The longest of the recent [`Self::frame_times`], None if none have been recorded
+    pub fn frame_time_max(&self) -> Option<Duration> {
+        self.frame_times().max()
+    }
+
+    /// The mean of the recent [`Self::frame_times`], None if none have been recorded
+    pub fn frame_time_avg(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frame_times.len())
+            .ok()
+            .filter(|&n| n > 0)?;
+        Some(self.frame_times().sum::<Duration>() / count)
+    }
+
+    /// The `p`th percentile of the recent [`Self::frame_times`] using the nearest rank, None if none have been recorded.
+    ///
+    /// `p` is clamped from 0.0 to 100.0, so 50.0 is the median and 99.0 is the frame time that all but the slowest 1% of
+    /// frames were within.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rugfx::input::input_manager::InputManagerState;
+    ///
+    /// let mut input = InputManagerState::<()>::default();
+    /// input.frame_time_capacity = 100;
+    /// for _ in 0..3 {
+    ///     input.preupdate();
+    /// }
+    /// assert_eq!(input.frame_times().len(), 3);
+    /// assert_eq!(input.frame_time_percentile(0.0), input.frame_time_min());
+    /// assert_eq!(input.frame_time_percentile(100.0), input.frame_time_max());
+    /// assert!(input.frame_time_percentile(50.0) <= input.frame_time_max());
+    ///
+    /// input.reset_time();
+    /// assert_eq!(input.frame_time_percentile(50.0), None);
+    /// ```
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn frame_time_percentile(&self, p: f32) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = self.frame_times().collect();
+        sorted.sort_unstable();
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f32).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Clears all held, pressed and released input state, see [`RawInputManagerState::reset_input`].
+    ///
+    /// Bindings are left untouched.
+    pub fn reset_input(&mut self) {
+        self.raw.reset_input();
+    }
+
+    /// Returns true if the binding was pressed since the last update.
+    ///
+    /// A chord is pressed when all of its keys are held and at least one was pressed since the last update, see
+    /// [`Bindings::bind_chord`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::{bindings::Bindings, input_manager::InputManagerState};
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Fire,
+    ///     Special,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind(MouseButton::Left.into(), Binds::Fire);
+    /// bindings.bind_chord(vec![MouseButton::Left.into(), MouseButton::Right.into()], Binds::Special);
+    /// let mut input = InputManagerState::new(bindings);
+    /// let press = |button| WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state: ElementState::Pressed,
+    ///     button,
+    /// };
+    ///
+    /// input.raw.process_window_event(press(MouseButton::Left));
+    /// assert!(input.pressed(&Binds::Fire));
+    /// assert!(!input.pressed(&Binds::Special));
+    ///
+    /// input.raw.clear();
+    /// input.raw.process_window_event(press(MouseButton::Right));
+    /// assert!(input.pressed(&Binds::Special));
+    /// assert!(!input.pressed(&Binds::Fire));
+    /// ```
+    pub fn pressed(&self, input: &B) -> bool {
+        self.active_inputs(input).any(|k| self.raw.pressed(k))
+            || self.active_chords(input).any(|chord| {
+                chord.iter().all(|k| self.raw.held(k)) && chord.iter().any(|k| self.raw.pressed(k))
+            })
+    }
+
+    /// How many mouse wheel ticks of the [`Input::Scroll`] inputs bound to the binding happened since the last update.
+    ///
+    /// [`Self::pressed`] is true once however many lines were scrolled, use this to act once per tick such as cycling through
+    /// weapons. See [`RawInputManagerState::scroll_ticks`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::{bindings::Bindings, input_manager::InputManagerState, ScrollDirection};
+    /// use winit::event::{DeviceId, MouseScrollDelta, TouchPhase, WindowEvent};
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     NextWeapon,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind(ScrollDirection::Down.into(), Binds::NextWeapon);
+    /// let mut input = InputManagerState::new(bindings);
+    /// input.raw.process_window_event(WindowEvent::MouseWheel {
+    ///     device_id: DeviceId::dummy(),
+    ///     delta: MouseScrollDelta::LineDelta(0.0, -3.0),
+    ///     phase: TouchPhase::Moved,
+    /// });
+    /// assert!(input.pressed(&Binds::NextWeapon));
+    /// assert_eq!(input.scroll_ticks(&Binds::NextWeapon), 3);
+    /// ```
+    pub fn scroll_ticks(&self, input: &B) -> u32 {
+        self.active_inputs(input)
+            .map(|k| match k {
+                Input::Scroll(direction) => self.raw.scroll_ticks(*direction),
+                _ => 0,
+            })
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Returns true when the binding is pressed, then repeatedly every `repeat_interval` once it has been held for `initial_delay`.
+    ///
+    /// Like key repeat in a text field but deterministic, useful for navigating menus by holding a direction. At most one repeat is
+    /// reported per update. The repeats are timed from the update the binding was pressed in using the unscaled
+    /// [`RawInputManagerState::game_time`], so they aren't affected by the `time_scale` and calling this several times in an
+    /// update gives the same result. Call it every update, or at least on the update the binding is pressed.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use rugfx::input::{bindings::Bindings, input_manager::InputManagerState};
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// #[derive(Clone, PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Down,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind(MouseButton::Left.into(), Binds::Down);
+    /// let mut input = InputManagerState::new(bindings);
+    /// let click = |state| WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state,
+    ///     button: MouseButton::Left,
+    /// };
+    ///
+    /// let (delay, interval) = (Duration::from_millis(300), Duration::from_millis(200));
+    /// let start = Instant::now();
+    /// let mut repeats = Vec::new();
+    /// for update in 0..12 {
+    ///     match update {
+    ///         0 => input.raw.process_window_event(click(ElementState::Pressed)),
+    ///         10 => input.raw.process_window_event(click(ElementState::Released)),
+    ///         _ => (),
+    ///     }
+    ///     // 100ms per update
+    ///     input.raw.preupdate_at(start + Duration::from_millis(100) * update);
+    ///     let repeat = input.pressed_repeating(&Binds::Down, delay, interval);
+    ///     assert_eq!(repeat, input.pressed_repeating(&Binds::Down, delay, interval));
+    ///     repeats.push(repeat);
+    ///     input.raw.clear();
+    /// }
+    /// let fired: Vec<usize> = (0..repeats.len()).filter(|&update| repeats[update]).collect();
+    /// assert_eq!(fired, [0, 3, 5, 7, 9]);
+    /// ```
+    pub fn pressed_repeating(
+        &mut self,
+        input: &B,
+        initial_delay: Duration,
+        repeat_interval: Duration,
+    ) -> bool
+    where
+        B: Clone,
+    {
+        let now = self.raw.game_time();
+        if self.pressed(input) {
+            self.repeat_starts.insert(input.clone(), now);
+            return true;
+        }
+        if !self.held(input) {
+            self.repeat_starts.remove(input);
+            return false;
+        }
+        let Some(&start) = self.repeat_starts.get(input) else {
+            return false;
+        };
+        let previous = now.saturating_sub(self.raw.delta_time());
+        let repeats = |time: Duration| {
+            let Some(since_delay) = time
+                .checked_sub(start)
+                .and_then(|held| held.checked_sub(initial_delay))
+            else {
+                return 0;
+            };
+            // A zero interval repeats every update once the delay has passed
+            1 + since_delay
+                .as_nanos()
+                .checked_div(repeat_interval.as_nanos())
+                .unwrap_or(time.as_nanos())
+        };
+        repeats(now) > repeats(previous)
     }
 
     /// Returns true if the binding was held at any point since the last update
     pub fn held(&self, input: &B) -> bool {
-        self.bindings
-            .transform(input)
-            .iter()
-            .any(|k| self.raw.held(k))
+        self.active_inputs(input).any(|k| self.raw.held(k))
+            || self
+                .active_chords(input)
+                .any(|chord| chord.iter().all(|k| self.raw.held(k)))
+    }
+
+    /// Returns every binding that is currently held, such as for a debug overlay listing the active actions.
+    ///
+    /// This checks every bound key of every binding so it is O(n) in the number of bindings, prefer [`Self::held`] for
+    /// gameplay code.
+    pub fn held_binds(&self) -> Vec<&B> {
+        self.bindings.binds().filter(|b| self.held(b)).collect()
     }
 
     /// Returns true if the binding as released since the last update
     pub fn released(&self, input: &B) -> bool {
-        self.bindings
-            .transform(input)
-            .iter()
-            .any(|k| self.raw.released(k))
+        self.active_inputs(input).any(|k| self.raw.released(k))
+            || self.active_chords(input).any(|chord| {
+                chord.iter().any(|k| self.raw.released(k))
+                    && chord
+                        .iter()
+                        .all(|k| self.raw.held(k) || self.raw.released(k))
+            })
+    }
+
+    /// Returns the device of the most recently pressed key bound to the binding, or [`None`] if none have been pressed.
+    ///
+    /// Useful for showing button prompts for the device the player is using. The keys of chords bound to the binding count too,
+    /// and like [`Self::pressed`] nothing is reported while input is disabled, see [`Self::set_enabled`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::{bindings::Bindings, input_manager::InputManagerState, InputDevice};
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Special,
+    /// }
+    ///
+    /// let mut bindings = Bindings::default();
+    /// bindings.bind_chord(vec![MouseButton::Left.into(), MouseButton::Right.into()], Binds::Special);
+    /// let mut input = InputManagerState::new(bindings);
+    /// input.raw.process_window_event(WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state: ElementState::Pressed,
+    ///     button: MouseButton::Left,
+    /// });
+    /// assert_eq!(input.last_trigger_device(&Binds::Special), Some(InputDevice::Mouse));
+    ///
+    /// input.set_enabled(false);
+    /// assert_eq!(input.last_trigger_device(&Binds::Special), None);
+    /// ```
+    pub fn last_trigger_device(&self, input: &B) -> Option<InputDevice> {
+        self.active_inputs(input)
+            .chain(self.active_chords(input).flatten())
+            .filter_map(|k| Some((self.raw.last_press_time(k)?, k)))
+            .max_by_key(|(time, _)| *time)
+            .map(|(_, k)| k.device())
     }
 
     /// The mouse motion since the last update multiplied by the mouse sensitivity
     pub fn mouse_motion(&self) -> (f64, f64) {
+        if !self.enabled {
+            return (0.0, 0.0);
+        }
         let m = self.raw.mouse_motion();
         (
             m[0] * self.mouse_sensitivity[0],
@@ -77,6 +537,32 @@ impl<B: InputBind> InputManagerState<B> {
         )
     }
 
+    /// The accumulated game time since the start of the event loop, scaled by the `time_scale`.
+    ///
+    /// This only advances by the scaled [`Self::delta_time`] each update, so it runs slower when the `time_scale` is below 1.0 and
+    /// stops while the game is paused by setting it to 0. Use it for gameplay such as animations and cooldowns. Restart it with
+    /// [`Self::reset_time`].
+    ///
+    /// [`RawInputManagerState::game_time`] is the unscaled wall-clock time instead, which keeps advancing while paused and is
+    /// restarted with [`RawInputManagerState::reset_game_time`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rugfx::input::input_manager::InputManagerState;
+    ///
+    /// let mut input = InputManagerState::<()>::default();
+    /// input.time_scale = 0.0;
+    /// input.preupdate();
+    /// std::thread::sleep(Duration::from_millis(1));
+    /// input.preupdate();
+    /// assert_eq!(input.game_time(), Duration::ZERO);
+    /// assert!(input.raw.game_time() > Duration::ZERO);
+    /// ```
+    pub fn game_time(&self) -> Duration {
+        self.time
+    }
+
     /// Returns the time between the last update and the update before it taking into account the `time_scale`.
     pub fn delta_time(&self) -> Duration {
         self.raw.delta_time().mul_f32(self.time_scale)
@@ -96,11 +582,167 @@ impl<B: InputBind> InputManagerState<B> {
         self.delta_time().as_secs_f64()
     }
 
+    /// The factor to interpolate by this update to exponentially approach a target at `rate`, independent of the frame rate.
+    ///
+    /// Computed as `1 - exp(-rate * dt)` using the scaled [`Self::delta_time`]. A naive `lerp(current, target, 0.1)` each frame
+    /// moves faster at higher frame rates, instead use `lerp(current, target, input.smoothing_factor(rate))`. Higher rates
+    /// approach the target faster, after `1 / rate` seconds about 63% of the distance has been covered.
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::input_manager::InputManagerState;
+    /// # let input = InputManagerState::<()>::default();
+    /// let (mut camera, target) = (0.0, 10.0);
+    /// camera += (target - camera) * input.smoothing_factor(5.0);
+    /// ```
+    pub fn smoothing_factor(&self, rate: f32) -> f32 {
+        1.0 - (-rate * self.delta_time_f32()).exp()
+    }
+
+    /// Returns the wall-clock time between the last update and the update before it, ignoring the `time_scale`.
+    ///
+    /// Useful for things that should keep running at normal speed when the game is slowed down or paused, such as UI animations.
+    /// Equivalent to [`RawInputManagerState::delta_time`].
+    pub fn unscaled_delta_time(&self) -> Duration {
+        self.raw.delta_time()
+    }
+
+    /// Returns the wall-clock time between the last update and the update before it, ignoring the `time_scale`, as an f32.
+    ///
+    /// Equivalent to [`Self::unscaled_delta_time`] followed by [`Duration::as_secs_f32`]
+    pub fn unscaled_delta_time_f32(&self) -> f32 {
+        self.unscaled_delta_time().as_secs_f32()
+    }
+
+    /// Returns the wall-clock time between the last update and the update before it, ignoring the `time_scale`, as an f64.
+    ///
+    /// Equivalent to [`Self::unscaled_delta_time`] followed by [`Duration::as_secs_f64`]
+    pub fn unscaled_delta_time_f64(&self) -> f64 {
+        self.unscaled_delta_time().as_secs_f64()
+    }
+
     /// Get the 1-D axis
+    ///
+    /// Held keys count as 1.0 and analog sources bound with [`Bindings::bind_analog`] as their scaled value, the strongest of
+    /// these is used for each direction so keyboard and gamepad can be used at the same time.
     #[expect(clippy::needless_pass_by_value)]
     pub fn axis(&self, bind: AxisBind<B>) -> f32 {
-        (if self.held(bind.pos) { 1.0 } else { 0.0 })
-            - (if self.held(bind.neg) { 1.0 } else { 0.0 })
+        self.analog_value(bind.pos) - self.analog_value(bind.neg)
+    }
+
+    /// The strength of a binding in its positive direction, 1.0 if a key is held otherwise the strongest analog source
+    fn analog_value(&self, input: &B) -> f32 {
+        let digital = if self.held(input) { 1.0 } else { 0.0 };
+        if !self.enabled {
+            return digital;
+        }
+        self.bindings
+            .analog(input)
+            .iter()
+            .map(|(source, scale)| self.axis_source(*source) * scale)
+            .fold(digital, f32::max)
+    }
+
+    /// Gamepad axis values with a magnitude at most this are treated as zero, to stop sticks that don't rest exactly at the
+    /// center from drifting. Clamped to 0.0 to 0.99, defaults to 0.0.
+    ///
+    /// The dead zone is applied to each axis on its own, so a stick only moving along one axis is exactly zero on the other.
+    /// It is applied before [`Self::axis_n_norm`] limits the length. Keys and mouse sources are not affected.
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = if dead_zone.is_nan() {
+            0.0
+        } else {
+            dead_zone.clamp(0.0, 0.99)
+        };
+    }
+
+    /// The dead zone set with [`Self::set_dead_zone`]
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// Applies the dead zone and [`Self::response_curve`] to an axis value from -1.0 to 1.0.
+    ///
+    /// Values outside the dead zone are rescaled so the output still starts at 0.0 and reaches 1.0 at the edge.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::input_manager::{InputManagerState, ResponseCurve};
+    ///
+    /// let mut input = InputManagerState::<()>::default();
+    /// input.set_dead_zone(0.5);
+    /// assert_eq!(input.shape_axis(0.25), 0.0);
+    /// assert_eq!(input.shape_axis(-0.75), -0.5);
+    /// assert_eq!(input.shape_axis(1.0), 1.0);
+    ///
+    /// input.response_curve = ResponseCurve::Quadratic;
+    /// assert_eq!(input.shape_axis(-0.75), -0.25);
+    /// ```
+    pub fn shape_axis(&self, value: f32) -> f32 {
+        let magnitude = value.abs().min(1.0);
+        if magnitude <= self.dead_zone {
+            return 0.0;
+        }
+        let rescaled = (magnitude - self.dead_zone) / (1.0 - self.dead_zone);
+        self.response_curve.apply(rescaled).copysign(value)
+    }
+
+    /// The current value of an analog source, gamepad axes are shaped by [`Self::shape_axis`]
+    #[expect(clippy::cast_possible_truncation)]
+    pub fn axis_source(&self, source: AxisSource) -> f32 {
+        let (motion_x, motion_y) = self.mouse_motion();
+        let [wheel_x, wheel_y] = self.raw.mouse_wheel_delta();
+        match source {
+            AxisSource::MouseMotionX => motion_x as f32,
+            AxisSource::MouseMotionY => motion_y as f32,
+            AxisSource::MouseWheelX => wheel_x,
+            AxisSource::MouseWheelY => wheel_y,
+            #[cfg(feature = "gamepad")]
+            AxisSource::Gamepad(axis) => self.shape_axis(self.raw.gamepad_axis(axis)),
+        }
+    }
+
+    /// Get the 1-D axis as exactly -1, 0 or 1, useful for grid based movement
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::{
+    ///     dry_binds,
+    ///     input::{bindings::AxisBind, input_manager::InputManagerState},
+    /// };
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// enum Binds {
+    ///     Right,
+    ///     Left,
+    /// }
+    ///
+    /// let mut input = InputManagerState::new(dry_binds! {
+    ///     MouseButton::Right => Binds::Right,
+    ///     MouseButton::Left => Binds::Left,
+    /// });
+    /// let axis = || AxisBind { pos: &Binds::Right, neg: &Binds::Left };
+    /// assert_eq!(input.axis_int(axis()), 0);
+    ///
+    /// let press = |button| WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state: ElementState::Pressed,
+    ///     button,
+    /// };
+    /// input.raw.process_window_event(press(MouseButton::Left));
+    /// assert_eq!(input.axis_int(axis()), -1);
+    /// input.raw.process_window_event(press(MouseButton::Right));
+    /// assert_eq!(input.axis_int(axis()), 0);
+    /// ```
+    #[expect(clippy::needless_pass_by_value)]
+    pub fn axis_int(&self, bind: AxisBind<B>) -> i32 {
+        i32::from(self.held(bind.pos)) - i32::from(self.held(bind.neg))
+    }
+
+    /// Get the N-D axis as exactly -1, 0 or 1 in each dimension
+    pub fn axis_n_int<const N: usize>(&self, binds: [AxisBind<B>; N]) -> [i32; N] {
+        binds.map(|axis| self.axis_int(axis))
     }
 
     /// Get the N-D axis
@@ -108,22 +750,68 @@ impl<B: InputBind> InputManagerState<B> {
         binds.map(|axis| self.axis(axis))
     }
 
-    /// Get the N-D axis with the length of 1 or 0
+    /// Get the N-D axis with its length limited to 1, so diagonal movement isn't faster
+    ///
+    /// Analog values with a length less than 1 are left as is so a stick pushed halfway still moves at half speed. The dead
+    /// zone is applied to each axis before the length is limited, see [`Self::set_dead_zone`].
     pub fn axis_n_norm<const N: usize>(&self, binds: [AxisBind<B>; N]) -> [f32; N] {
         let axes = self.axis_n(binds);
         let sqr_mag: f32 = axes.iter().map(|x| x * x).sum();
-        if sqr_mag == 0.0 {
-            [0.0; N]
+        if sqr_mag <= 1.0 {
+            axes
         } else {
             let m = sqr_mag.sqrt();
             axes.map(|x| x / m)
         }
     }
 
+    /// Returns true once for every `duration` of scaled [`Self::game_time`] that elapses, call it every update with the same duration.
+    ///
+    /// The first interval starts at the first call with each duration. If an update is longer than the interval, the
+    /// missed intervals are returned on the following calls, so none are skipped. A zero duration returns true every call.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rugfx::input::input_manager::InputManagerState;
+    ///
+    /// let mut input = InputManagerState::<()>::default();
+    /// let interval = Duration::from_millis(1);
+    /// assert!(!input.tick_every(interval));
+    /// std::thread::sleep(Duration::from_millis(3));
+    /// input.preupdate();
+    /// let ticks = (0..1000).filter(|_| input.tick_every(interval)).count();
+    /// assert_eq!(ticks as u128, input.game_time().as_millis());
+    /// ```
+    pub fn tick_every(&mut self, duration: Duration) -> bool {
+        let now = self.time;
+        tick_interval(&mut self.interval_timers, duration, now)
+    }
+
     /// Returns [`true`] every [`time`] interval measured in seconds
     #[cfg(feature = "unstable")]
+    #[deprecated(
+        note = "skips or repeats intervals depending on the frame rate, use `tick_every` instead"
+    )]
     pub fn every(&self, time: f32) -> bool {
-        // TODO: The soundness of the floating point number maths here needs to be verified that every is called every time
         self.time.as_secs_f32() % time < self.delta_time_f32()
     }
 }
+
+/// Returns true if `duration` has elapsed since the interval last fired at time `now`, advancing it by one interval
+pub(crate) fn tick_interval(
+    timers: &mut HashMap<Duration, Duration>,
+    duration: Duration,
+    now: Duration,
+) -> bool {
+    let last = timers.entry(duration).or_insert(now);
+    if duration.is_zero() {
+        return true;
+    }
+    if now.saturating_sub(*last) >= duration {
+        *last += duration;
+        true
+    } else {
+        false
+    }
+}
@@ -0,0 +1,228 @@
+use winit::keyboard::KeyCode;
+
+/// Glyph id used for keys that have no known glyph
+pub const UNKNOWN_KEY_GLYPH_ID: &str = "kbd_unknown";
+
+macro_rules! key_codes {
+    ($($code:ident => $glyph:literal,)*) => {
+        pub fn glyph_id(code: KeyCode) -> &'static str {
+            match code {
+                $(KeyCode::$code => $glyph,)*
+                _ => UNKNOWN_KEY_GLYPH_ID,
+            }
+        }
+
+        /// The name of the key code as written in the winit source, such as `"KeyW"`
+        pub fn name(code: KeyCode) -> Option<&'static str> {
+            match code {
+                $(KeyCode::$code => Some(stringify!($code)),)*
+                _ => None,
+            }
+        }
+
+        /// The inverse of [`name`]
+        pub fn from_name(name: &str) -> Option<KeyCode> {
+            match name {
+                $(stringify!($code) => Some(KeyCode::$code),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+key_codes! {
+    Backquote => "kbd_backquote",
+    Backslash => "kbd_backslash",
+    BracketLeft => "kbd_bracket_left",
+    BracketRight => "kbd_bracket_right",
+    Comma => "kbd_comma",
+    Digit0 => "kbd_0",
+    Digit1 => "kbd_1",
+    Digit2 => "kbd_2",
+    Digit3 => "kbd_3",
+    Digit4 => "kbd_4",
+    Digit5 => "kbd_5",
+    Digit6 => "kbd_6",
+    Digit7 => "kbd_7",
+    Digit8 => "kbd_8",
+    Digit9 => "kbd_9",
+    Equal => "kbd_equal",
+    IntlBackslash => "kbd_intl_backslash",
+    IntlRo => "kbd_intl_ro",
+    IntlYen => "kbd_intl_yen",
+    KeyA => "kbd_a",
+    KeyB => "kbd_b",
+    KeyC => "kbd_c",
+    KeyD => "kbd_d",
+    KeyE => "kbd_e",
+    KeyF => "kbd_f",
+    KeyG => "kbd_g",
+    KeyH => "kbd_h",
+    KeyI => "kbd_i",
+    KeyJ => "kbd_j",
+    KeyK => "kbd_k",
+    KeyL => "kbd_l",
+    KeyM => "kbd_m",
+    KeyN => "kbd_n",
+    KeyO => "kbd_o",
+    KeyP => "kbd_p",
+    KeyQ => "kbd_q",
+    KeyR => "kbd_r",
+    KeyS => "kbd_s",
+    KeyT => "kbd_t",
+    KeyU => "kbd_u",
+    KeyV => "kbd_v",
+    KeyW => "kbd_w",
+    KeyX => "kbd_x",
+    KeyY => "kbd_y",
+    KeyZ => "kbd_z",
+    Minus => "kbd_minus",
+    Period => "kbd_period",
+    Quote => "kbd_quote",
+    Semicolon => "kbd_semicolon",
+    Slash => "kbd_slash",
+    AltLeft => "kbd_alt_left",
+    AltRight => "kbd_alt_right",
+    Backspace => "kbd_backspace",
+    CapsLock => "kbd_caps_lock",
+    ContextMenu => "kbd_context_menu",
+    ControlLeft => "kbd_control_left",
+    ControlRight => "kbd_control_right",
+    Enter => "kbd_enter",
+    SuperLeft => "kbd_super_left",
+    SuperRight => "kbd_super_right",
+    ShiftLeft => "kbd_shift_left",
+    ShiftRight => "kbd_shift_right",
+    Space => "kbd_space",
+    Tab => "kbd_tab",
+    Convert => "kbd_convert",
+    KanaMode => "kbd_kana_mode",
+    Lang1 => "kbd_lang_1",
+    Lang2 => "kbd_lang_2",
+    Lang3 => "kbd_lang_3",
+    Lang4 => "kbd_lang_4",
+    Lang5 => "kbd_lang_5",
+    NonConvert => "kbd_non_convert",
+    Delete => "kbd_delete",
+    End => "kbd_end",
+    Help => "kbd_help",
+    Home => "kbd_home",
+    Insert => "kbd_insert",
+    PageDown => "kbd_page_down",
+    PageUp => "kbd_page_up",
+    ArrowDown => "kbd_arrow_down",
+    ArrowLeft => "kbd_arrow_left",
+    ArrowRight => "kbd_arrow_right",
+    ArrowUp => "kbd_arrow_up",
+    NumLock => "kbd_num_lock",
+    Numpad0 => "kbd_numpad_0",
+    Numpad1 => "kbd_numpad_1",
+    Numpad2 => "kbd_numpad_2",
+    Numpad3 => "kbd_numpad_3",
+    Numpad4 => "kbd_numpad_4",
+    Numpad5 => "kbd_numpad_5",
+    Numpad6 => "kbd_numpad_6",
+    Numpad7 => "kbd_numpad_7",
+    Numpad8 => "kbd_numpad_8",
+    Numpad9 => "kbd_numpad_9",
+    NumpadAdd => "kbd_numpad_add",
+    NumpadBackspace => "kbd_numpad_backspace",
+    NumpadClear => "kbd_numpad_clear",
+    NumpadClearEntry => "kbd_numpad_clear_entry",
+    NumpadComma => "kbd_numpad_comma",
+    NumpadDecimal => "kbd_numpad_decimal",
+    NumpadDivide => "kbd_numpad_divide",
+    NumpadEnter => "kbd_numpad_enter",
+    NumpadEqual => "kbd_numpad_equal",
+    NumpadHash => "kbd_numpad_hash",
+    NumpadMemoryAdd => "kbd_numpad_memory_add",
+    NumpadMemoryClear => "kbd_numpad_memory_clear",
+    NumpadMemoryRecall => "kbd_numpad_memory_recall",
+    NumpadMemoryStore => "kbd_numpad_memory_store",
+    NumpadMemorySubtract => "kbd_numpad_memory_subtract",
+    NumpadMultiply => "kbd_numpad_multiply",
+    NumpadParenLeft => "kbd_numpad_paren_left",
+    NumpadParenRight => "kbd_numpad_paren_right",
+    NumpadStar => "kbd_numpad_star",
+    NumpadSubtract => "kbd_numpad_subtract",
+    Escape => "kbd_escape",
+    Fn => "kbd_fn",
+    FnLock => "kbd_fn_lock",
+    PrintScreen => "kbd_print_screen",
+    ScrollLock => "kbd_scroll_lock",
+    Pause => "kbd_pause",
+    BrowserBack => "kbd_browser_back",
+    BrowserFavorites => "kbd_browser_favorites",
+    BrowserForward => "kbd_browser_forward",
+    BrowserHome => "kbd_browser_home",
+    BrowserRefresh => "kbd_browser_refresh",
+    BrowserSearch => "kbd_browser_search",
+    BrowserStop => "kbd_browser_stop",
+    Eject => "kbd_eject",
+    LaunchApp1 => "kbd_launch_app_1",
+    LaunchApp2 => "kbd_launch_app_2",
+    LaunchMail => "kbd_launch_mail",
+    MediaPlayPause => "kbd_media_play_pause",
+    MediaSelect => "kbd_media_select",
+    MediaStop => "kbd_media_stop",
+    MediaTrackNext => "kbd_media_track_next",
+    MediaTrackPrevious => "kbd_media_track_previous",
+    Power => "kbd_power",
+    Sleep => "kbd_sleep",
+    AudioVolumeDown => "kbd_audio_volume_down",
+    AudioVolumeMute => "kbd_audio_volume_mute",
+    AudioVolumeUp => "kbd_audio_volume_up",
+    WakeUp => "kbd_wake_up",
+    Meta => "kbd_meta",
+    Hyper => "kbd_hyper",
+    Turbo => "kbd_turbo",
+    Abort => "kbd_abort",
+    Resume => "kbd_resume",
+    Suspend => "kbd_suspend",
+    Again => "kbd_again",
+    Copy => "kbd_copy",
+    Cut => "kbd_cut",
+    Find => "kbd_find",
+    Open => "kbd_open",
+    Paste => "kbd_paste",
+    Props => "kbd_props",
+    Select => "kbd_select",
+    Undo => "kbd_undo",
+    Hiragana => "kbd_hiragana",
+    Katakana => "kbd_katakana",
+    F1 => "kbd_f1",
+    F2 => "kbd_f2",
+    F3 => "kbd_f3",
+    F4 => "kbd_f4",
+    F5 => "kbd_f5",
+    F6 => "kbd_f6",
+    F7 => "kbd_f7",
+    F8 => "kbd_f8",
+    F9 => "kbd_f9",
+    F10 => "kbd_f10",
+    F11 => "kbd_f11",
+    F12 => "kbd_f12",
+    F13 => "kbd_f13",
+    F14 => "kbd_f14",
+    F15 => "kbd_f15",
+    F16 => "kbd_f16",
+    F17 => "kbd_f17",
+    F18 => "kbd_f18",
+    F19 => "kbd_f19",
+    F20 => "kbd_f20",
+    F21 => "kbd_f21",
+    F22 => "kbd_f22",
+    F23 => "kbd_f23",
+    F24 => "kbd_f24",
+    F25 => "kbd_f25",
+    F26 => "kbd_f26",
+    F27 => "kbd_f27",
+    F28 => "kbd_f28",
+    F29 => "kbd_f29",
+    F30 => "kbd_f30",
+    F31 => "kbd_f31",
+    F32 => "kbd_f32",
+    F33 => "kbd_f33",
+    F34 => "kbd_f34",
+    F35 => "kbd_f35",
+}
@@ -1,39 +1,109 @@
 use std::{
-    collections::HashSet,
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     time::{Duration, Instant},
 };
 
 use winit::{
     application::ApplicationHandler,
     dpi::PhysicalSize,
-    event::{DeviceEvent, ElementState, MouseScrollDelta, StartCause, WindowEvent},
+    event::{
+        DeviceEvent, ElementState, Ime, KeyEvent, MouseButton, MouseScrollDelta, StartCause,
+        TouchPhase, WindowEvent,
+    },
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
+    window::WindowId,
 };
 
-use super::Input;
+#[cfg(feature = "gamepad")]
+use super::gamepad::{GamepadAxis, Gamepads};
+use super::{
+    input_manager::tick_interval,
+    replay::{InputRecord, InputReplay, Recorder},
+    snapshot::InputSnapshot,
+    touch::TouchPoint,
+    Input, ScrollDirection,
+};
 
 /// Stores state about keys, mouse motion, timing and other window events.
 pub struct RawInputManager<H> {
     pub handler: H,
     state: RawInputManagerState,
+    replay: Option<InputReplay>,
 }
 
 #[derive(Debug)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct RawInputManagerState {
-    keys_held: HashSet<Input>,
-    keys_pressed: HashSet<Input>,
+    /// When true every resize is recorded in [`Self::resizes`] instead of only the latest in [`Self::resized`]. Defaults to false.
+    pub accumulate_resizes: bool,
+    /// The largest magnitude mouse motion and scroll can accumulate to between updates. Defaults to 10000.
+    ///
+    /// Protects against huge jumps if updates stop for a while, normally [`Self::clear`] runs every update so this is never reached.
+    pub max_delta_per_frame: f64,
+    /// How many recent cursor positions are kept in [`Self::cursor_history`]. Defaults to 0 which disables the history.
+    pub cursor_history_capacity: usize,
+    /// The longest time between presses of a mouse button that count as a multi click. Defaults to 400ms.
+    pub multi_click_interval: Duration,
+
+    /// Records presses and releases while recording, see [`Self::start_recording`]
+    recorder: Option<Recorder>,
+    /// The window the event being processed was sent to, see [`Self::process_window_event_for`]
+    event_window: Option<WindowId>,
+    /// The window each held or just released input was pressed in
+    input_windows: HashMap<Input, WindowId>,
+    focused_window: Option<WindowId>,
+    /// The last cursor position within each window the cursor has moved over
+    window_mouse_positions: HashMap<WindowId, [f64; 2]>,
+    /// The held inputs and when they were pressed
+    keys_held: HashMap<Input, Instant>,
+    keys_pressed: HashMap<Input, Instant>,
     keys_released: HashSet<Input>,
+    last_press: HashMap<Input, Instant>,
+    modifiers: ModifiersState,
+    text_input: String,
+    /// The logical key each held physical key typed when it was pressed, so it is released even if the layout changed
+    logical_keys: HashMap<PhysicalKey, Input>,
+    ime_enabled: bool,
+    ime_preedit: Option<(String, Option<(usize, usize)>)>,
 
     mouse_motion: [f64; 2],
     mouse_position: [f64; 2],
+    last_mouse_position: Option<[f64; 2]>,
+    cursor_history: VecDeque<([f64; 2], Instant)>,
     mouse_wheel_delta: [f32; 2],
+    mouse_wheel_pixel_delta: [f64; 2],
+    /// The fraction of a line scrolled that hasn't pressed an [`Input::Scroll`] yet
+    scroll_remainder: [f32; 2],
+    /// The whole lines scrolled in each direction since the last update
+    scroll_ticks: HashMap<ScrollDirection, u32>,
+    mouse_press_positions: HashMap<MouseButton, [f64; 2]>,
+    /// When each mouse button was last pressed and how many clicks in a row that press was
+    mouse_clicks: HashMap<MouseButton, (Instant, u32)>,
+    pinch_delta: f64,
+    rotation_delta: f64,
+    touches: HashMap<u64, TouchPoint>,
 
     start: Instant,
     last_update: Instant,
     update_delta: Duration,
+    /// The game time each interval passed to [`Self::tick_every`] last fired
+    interval_timers: HashMap<Duration, Duration>,
 
     resize: Option<PhysicalSize<u32>>,
+    resizes: Vec<PhysicalSize<u32>>,
     close_requested: bool,
+    needs_redraw: bool,
     loop_exiting: bool,
+    first_frame: bool,
+    occluded: bool,
+    minimized: bool,
+    scale_factor: f64,
+    scale_factor_changed: bool,
+
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<Gamepads>,
+    #[cfg(feature = "gamepad")]
+    gamepad_axes: HashMap<GamepadAxis, f32>,
 }
 
 impl<H: RawInputHandler> ApplicationHandler for RawInputManager<H> {
@@ -44,10 +114,21 @@ impl<H: RawInputHandler> ApplicationHandler for RawInputManager<H> {
     fn window_event(
         &mut self,
         _event_loop: &winit::event_loop::ActiveEventLoop,
-        _window_id: winit::window::WindowId,
+        window_id: WindowId,
         event: WindowEvent,
     ) {
-        self.state.process_window_event(event);
+        if self.replay.is_some()
+            && matches!(
+                event,
+                WindowEvent::KeyboardInput { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::MouseWheel { .. }
+            )
+        {
+            // Live presses would make the replay diverge from the recording
+            return;
+        }
+        self.state.process_window_event_for(window_id, event);
     }
 
     fn new_events(
@@ -56,6 +137,12 @@ impl<H: RawInputHandler> ApplicationHandler for RawInputManager<H> {
         cause: winit::event::StartCause,
     ) {
         self.state.preupdate();
+        if let Some(replay) = &mut self.replay {
+            replay.apply(&mut self.state);
+            if replay.is_finished() {
+                self.replay = None;
+            }
+        }
         self.handler.update(event_loop, &self.state);
         // We can't draw on the StartCause::Init new_events because resume has not been called and hence created the window
         if cause != StartCause::Init {
@@ -70,10 +157,7 @@ impl<H: RawInputHandler> ApplicationHandler for RawInputManager<H> {
         _device_id: winit::event::DeviceId,
         event: DeviceEvent,
     ) {
-        if let DeviceEvent::MouseMotion { delta } = event {
-            self.state.mouse_motion[0] += delta.0;
-            self.state.mouse_motion[1] += delta.1;
-        }
+        self.state.process_device_event(&event);
     }
 
     fn exiting(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
@@ -100,102 +184,737 @@ impl<H: RawInputHandler> RawInputManager<H> {
         Self {
             handler,
             state: RawInputManagerState::default(),
+            replay: None,
         }
     }
+
+    /// Replays inputs recorded with [`RawInputManagerState::start_recording`], one recorded frame per update.
+    ///
+    /// Live keyboard, mouse button and mouse wheel events are ignored until the replay finishes, as scrolling presses
+    /// [`crate::input::Input::Scroll`], see [`InputReplay`].
+    pub fn replay(&mut self, records: Vec<InputRecord>) {
+        self.replay = Some(InputReplay::new(records));
+    }
+
+    /// Returns true while a replay started with [`Self::replay`] is running
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// The input state
+    pub fn state(&self) -> &RawInputManagerState {
+        &self.state
+    }
+
+    /// The input state, mutable to allow changing its settings
+    pub fn state_mut(&mut self) -> &mut RawInputManagerState {
+        &mut self.state
+    }
 }
 
 impl Default for RawInputManagerState {
     fn default() -> Self {
         Self {
-            keys_held: HashSet::default(),
-            keys_pressed: HashSet::default(),
+            accumulate_resizes: false,
+            max_delta_per_frame: 10_000.0,
+            cursor_history_capacity: 0,
+            multi_click_interval: Duration::from_millis(400),
+
+            recorder: None,
+            event_window: None,
+            input_windows: HashMap::default(),
+            focused_window: None,
+            window_mouse_positions: HashMap::default(),
+            keys_held: HashMap::default(),
+            keys_pressed: HashMap::default(),
             keys_released: HashSet::default(),
+            last_press: HashMap::default(),
+            modifiers: ModifiersState::default(),
+            text_input: String::new(),
+            logical_keys: HashMap::default(),
+            ime_enabled: false,
+            ime_preedit: None,
             mouse_motion: [0.0, 0.0],
             mouse_position: [0.0, 0.0],
+            last_mouse_position: None,
+            cursor_history: VecDeque::new(),
             mouse_wheel_delta: [0.0, 0.0],
+            mouse_wheel_pixel_delta: [0.0, 0.0],
+            scroll_remainder: [0.0, 0.0],
+            scroll_ticks: HashMap::default(),
+            mouse_press_positions: HashMap::default(),
+            mouse_clicks: HashMap::default(),
+            pinch_delta: 0.0,
+            rotation_delta: 0.0,
+            touches: HashMap::default(),
 
             start: Instant::now(),
             last_update: Instant::now(),
             update_delta: Duration::default(),
+            interval_timers: HashMap::default(),
 
             resize: None,
+            resizes: Vec::new(),
             close_requested: false,
+            needs_redraw: false,
             loop_exiting: false,
+            first_frame: true,
+            occluded: false,
+            minimized: false,
+            scale_factor: 1.0,
+            scale_factor_changed: false,
+
+            #[cfg(feature = "gamepad")]
+            gamepads: None,
+            #[cfg(feature = "gamepad")]
+            gamepad_axes: HashMap::default(),
         }
     }
 }
 
 impl RawInputManagerState {
+    /// Processes an event sent to a specific window, so it can be queried per window in multi-window applications.
+    ///
+    /// Presses are attributed to the window they were sent to, see [`Self::pressed_in`], and the cursor position is tracked
+    /// for each window, see [`Self::mouse_position_in`]. The window independent queries such as [`Self::pressed`] still
+    /// report events from every window. [`RawInputManager`] calls this with the id winit provides.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::{
+    ///     event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    ///     window::WindowId,
+    /// };
+    ///
+    /// let (editor, palette) = (WindowId::from(1), WindowId::from(2));
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event_for(palette, WindowEvent::Focused(true));
+    /// input.process_window_event_for(
+    ///     palette,
+    ///     WindowEvent::MouseInput {
+    ///         device_id: DeviceId::dummy(),
+    ///         state: ElementState::Pressed,
+    ///         button: MouseButton::Left,
+    ///     },
+    /// );
+    /// let left = MouseButton::Left.into();
+    /// assert_eq!(input.focused_window(), Some(palette));
+    /// assert!(input.pressed(&left));
+    /// assert!(input.pressed_in(palette, &left));
+    /// assert!(!input.pressed_in(editor, &left));
+    /// ```
+    pub fn process_window_event_for(&mut self, window_id: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::Focused(true) => self.focused_window = Some(window_id),
+            WindowEvent::Focused(false) | WindowEvent::Destroyed
+                if self.focused_window == Some(window_id) =>
+            {
+                self.focused_window = None;
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.window_mouse_positions
+                    .insert(window_id, [position.x, position.y]);
+            }
+            _ => (),
+        }
+        if matches!(event, WindowEvent::Destroyed) {
+            self.window_mouse_positions.remove(&window_id);
+        }
+        self.event_window = Some(window_id);
+        self.process_window_event(event);
+        self.event_window = None;
+    }
+
     pub fn process_window_event(&mut self, event: WindowEvent) {
+        if matches!(
+            event,
+            WindowEvent::KeyboardInput { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::CursorMoved { .. }
+                | WindowEvent::PinchGesture { .. }
+                | WindowEvent::RotationGesture { .. }
+                | WindowEvent::Touch(_)
+                | WindowEvent::Ime(_)
+                | WindowEvent::Resized(_)
+                | WindowEvent::ScaleFactorChanged { .. }
+                | WindowEvent::Focused(_)
+                | WindowEvent::Occluded(false)
+                | WindowEvent::RedrawRequested
+        ) {
+            self.needs_redraw = true;
+        }
         match event {
-            WindowEvent::KeyboardInput { event, .. } => {
-                self.update_input(event.physical_key.into(), event.state);
+            WindowEvent::KeyboardInput { event, .. } => self.process_key_event(&event),
+            WindowEvent::Ime(ime) => self.process_ime(ime),
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
             }
             WindowEvent::CloseRequested => {
                 self.close_requested = true;
             }
             WindowEvent::Resized(size) => {
                 self.resize = Some(size);
+                // Windows reports minimizing as a resize to zero and restoring as a resize back
+                self.minimized = size.width == 0 || size.height == 0;
+                if self.accumulate_resizes {
+                    self.resizes.push(size);
+                }
             }
             WindowEvent::CursorMoved { position, .. } => {
                 self.mouse_position = [position.x, position.y];
+                // The first position has nothing to move from
+                self.last_mouse_position.get_or_insert(self.mouse_position);
+                self.record_cursor_history();
             }
             WindowEvent::MouseWheel {
                 delta: MouseScrollDelta::LineDelta(x, y),
                 ..
             } => {
-                self.mouse_wheel_delta[0] += x;
-                self.mouse_wheel_delta[1] += y;
+                let limit = self.max_delta_per_frame;
+                accumulate_f32(&mut self.mouse_wheel_delta[0], x, limit);
+                accumulate_f32(&mut self.mouse_wheel_delta[1], y, limit);
+                self.press_scroll_lines(x, y);
+            }
+            WindowEvent::MouseWheel {
+                delta: MouseScrollDelta::PixelDelta(delta),
+                ..
+            } => {
+                let limit = self.max_delta_per_frame;
+                accumulate(&mut self.mouse_wheel_pixel_delta[0], delta.x, limit);
+                accumulate(&mut self.mouse_wheel_pixel_delta[1], delta.y, limit);
+            }
+            // winit documents that the pinch delta may be NaN
+            WindowEvent::PinchGesture { delta, .. } if delta.is_finite() => {
+                self.pinch_delta += delta;
+            }
+            WindowEvent::RotationGesture { delta, .. } => {
+                self.rotation_delta += f64::from(delta);
             }
             WindowEvent::MouseInput { button, state, .. } => {
+                if state == ElementState::Pressed && !self.held(&button.into()) {
+                    self.mouse_press_positions
+                        .insert(button, self.mouse_position);
+                    self.record_click(button);
+                }
                 self.update_input(button.into(), state);
             }
+            WindowEvent::Touch(touch) => {
+                self.touches.insert(touch.id, TouchPoint::from(&touch));
+            }
+            WindowEvent::Occluded(occluded) => {
+                self.occluded = occluded;
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = scale_factor;
+                self.scale_factor_changed = true;
+            }
+            // Focus moved to another window of the application that was already reported as focused
+            WindowEvent::Focused(false) if self.focused_window.is_some() => (),
             WindowEvent::Focused(false) => {
-                // When lost focus clear the keys held
-                self.keys_held.clear();
+                // Key releases are not delivered while unfocused, so release everything now to avoid stuck keys.
+                // Releases that arrive after regaining focus are ignored since the keys are no longer held.
+                for (input, _) in self.keys_held.drain() {
+                    self.keys_released.insert(input);
+                    if let Some(recorder) = &mut self.recorder {
+                        recorder.record(input, false);
+                    }
+                }
+                self.logical_keys.clear();
+                self.modifiers = ModifiersState::empty();
+            }
+            WindowEvent::Focused(true) => {
+                // Modifiers changed while unfocused are stale, winit reports the current ones after regaining focus
+                self.modifiers = ModifiersState::empty();
             }
             _ => (),
         }
     }
 
+    /// Processes mouse motion, which is reported as a device event so it is unaffected by the cursor being grabbed
+    ///
+    /// Non-finite deltas are ignored and the accumulated motion is clamped to [`Self::max_delta_per_frame`].
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::DeviceEvent;
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_device_event(&DeviceEvent::MouseMotion { delta: (f64::NAN, f64::INFINITY) });
+    /// assert_eq!(input.mouse_motion(), [0.0, 0.0]);
+    ///
+    /// input.process_device_event(&DeviceEvent::MouseMotion { delta: (1e300, -1e300) });
+    /// input.process_device_event(&DeviceEvent::MouseMotion { delta: (1e300, -1e300) });
+    /// let limit = input.max_delta_per_frame;
+    /// assert_eq!(input.mouse_motion(), [limit, -limit]);
+    /// ```
+    pub fn process_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta } = event {
+            let limit = self.max_delta_per_frame;
+            accumulate(&mut self.mouse_motion[0], delta.0, limit);
+            accumulate(&mut self.mouse_motion[1], delta.1, limit);
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Counts the whole lines scrolled in [`Self::scroll_ticks`] and presses the [`Input::Scroll`] of each direction
+    /// scrolled, keeping the fraction for the following events
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn press_scroll_lines(&mut self, x: f32, y: f32) {
+        let axes = [
+            (x, ScrollDirection::Left, ScrollDirection::Right),
+            (y, ScrollDirection::Up, ScrollDirection::Down),
+        ];
+        for ((delta, positive, negative), remainder) in axes.into_iter().zip(0..) {
+            if !delta.is_finite() {
+                continue;
+            }
+            let lines = &mut self.scroll_remainder[remainder];
+            *lines += delta;
+            let whole = lines.trunc();
+            *lines -= whole;
+            let direction = if whole > 0.0 {
+                positive
+            } else if whole < 0.0 {
+                negative
+            } else {
+                continue;
+            };
+            // Float to int casts saturate so huge deltas can't wrap
+            let ticks = self.scroll_ticks.entry(direction).or_default();
+            *ticks = ticks.saturating_add(whole.abs() as u32);
+            self.update_input(direction.into(), ElementState::Pressed);
+        }
+    }
+
+    fn record_cursor_history(&mut self) {
+        while self.cursor_history.len() >= self.cursor_history_capacity {
+            if self.cursor_history.pop_front().is_none() {
+                return;
+            }
+        }
+        self.cursor_history
+            .push_back((self.mouse_position, Instant::now()));
+    }
+
+    fn record_click(&mut self, button: MouseButton) {
+        let now = Instant::now();
+        let interval = self.multi_click_interval;
+        self.mouse_clicks
+            .entry(button)
+            .and_modify(|(last, count)| {
+                *count = if now.saturating_duration_since(*last) <= interval {
+                    *count + 1
+                } else {
+                    1
+                };
+                *last = now;
+            })
+            .or_insert((now, 1));
+    }
+
+    fn process_key_event(&mut self, event: &KeyEvent) {
+        match event.state {
+            ElementState::Pressed => {
+                if let Some(text) = &event.text {
+                    self.push_text(text);
+                }
+                if let (Entry::Vacant(entry), Some(input)) = (
+                    self.logical_keys.entry(event.physical_key),
+                    Input::from_key_event_logical(event),
+                ) {
+                    let input = *entry.insert(input);
+                    self.update_input(input, ElementState::Pressed);
+                }
+            }
+            ElementState::Released => {
+                if let Some(input) = self.logical_keys.remove(&event.physical_key) {
+                    self.update_input(input, ElementState::Released);
+                }
+            }
+        }
+        self.update_input(event.physical_key.into(), event.state);
+    }
+
+    fn process_ime(&mut self, ime: Ime) {
+        match ime {
+            Ime::Enabled => {
+                self.ime_enabled = true;
+            }
+            Ime::Preedit(text, cursor) => {
+                // An empty preedit means the composition was cleared
+                self.ime_preedit = (!text.is_empty()).then_some((text, cursor));
+            }
+            Ime::Commit(text) => {
+                self.ime_preedit = None;
+                self.push_text(&text);
+            }
+            Ime::Disabled => {
+                self.ime_enabled = false;
+                self.ime_preedit = None;
+            }
+        }
+    }
+
+    /// Appends typed text, control characters such as backspace and enter are left to the physical keys
+    fn push_text(&mut self, text: &str) {
+        self.text_input
+            .extend(text.chars().filter(|c| !c.is_control()));
+    }
+
     fn update_input(&mut self, input: Input, state: ElementState) {
-        match state {
+        let changed = match state {
             ElementState::Pressed => {
-                if self.keys_held.insert(input) {
-                    self.keys_pressed.insert(input);
+                if let Entry::Vacant(entry) = self.keys_held.entry(input) {
+                    let now = Instant::now();
+                    entry.insert(now);
+                    self.keys_pressed.insert(input, now);
+                    self.last_press.insert(input, now);
+                    if let Some(window) = self.event_window {
+                        self.input_windows.insert(input, window);
+                    } else {
+                        self.input_windows.remove(&input);
+                    }
+                    true
+                } else {
+                    false
                 }
             }
             ElementState::Released => {
-                if self.keys_held.remove(&input) {
+                let released = self.keys_held.remove(&input).is_some();
+                if released {
                     self.keys_released.insert(input);
                 }
+                released
             }
+        };
+        if let (true, Some(recorder)) = (changed, &mut self.recorder) {
+            recorder.record(input, state.is_pressed());
         }
     }
 
+    /// Applies a recorded press or release, see [`InputReplay`]
+    pub(crate) fn replay_record(&mut self, record: &InputRecord) {
+        let state = if record.pressed {
+            ElementState::Pressed
+        } else {
+            ElementState::Released
+        };
+        self.update_input(record.input, state);
+        self.needs_redraw = true;
+    }
+
+    /// Starts recording every press and release along with the update it happened in, replacing any recording in progress.
+    ///
+    /// Stop with [`Self::stop_recording`] and play the inputs back with [`InputReplay`], such as to reproduce a bug or in
+    /// tests. Inputs released by [`Self::reset_input`] are not recorded.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new());
+    }
+
+    /// Stops recording and returns the recorded inputs, empty if not recording
+    pub fn stop_recording(&mut self) -> Vec<InputRecord> {
+        self.recorder
+            .take()
+            .map(Recorder::finish)
+            .unwrap_or_default()
+    }
+
+    /// Returns true while recording, see [`Self::start_recording`]
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
     pub fn preupdate(&mut self) {
-        let now = Instant::now();
+        self.preupdate_at(Instant::now());
+    }
+
+    /// Like [`Self::preupdate`] but with the time of the update given, such as to step through updates at a fixed rate in
+    /// tests. Times before the last update are treated as the last update.
+    pub fn preupdate_at(&mut self, now: Instant) {
+        let now = now.max(self.last_update);
         self.update_delta = now.saturating_duration_since(self.last_update);
         self.last_update = now;
+        #[cfg(feature = "gamepad")]
+        self.process_gamepad_events();
+    }
+
+    /// Polls the gamepad backend and applies the button and axis changes since the last update
+    #[cfg(feature = "gamepad")]
+    fn process_gamepad_events(&mut self) {
+        use gilrs::EventType;
+
+        while let Some(event) = self.gamepads.as_mut().and_then(Gamepads::next_event) {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    self.update_input(button.into(), ElementState::Pressed);
+                }
+                EventType::ButtonReleased(button, _) => {
+                    self.update_input(button.into(), ElementState::Released);
+                }
+                EventType::AxisChanged(axis, value, _) if value.is_finite() => {
+                    self.gamepad_axes.insert(axis, value.clamp(-1.0, 1.0));
+                }
+                EventType::Disconnected => {
+                    // Releases won't arrive from a disconnected gamepad so release everything now to avoid stuck buttons
+                    let held: Vec<Input> = self
+                        .keys_held
+                        .keys()
+                        .filter(|input| matches!(input, Input::Gamepad(_)))
+                        .copied()
+                        .collect();
+                    for input in held {
+                        self.update_input(input, ElementState::Released);
+                    }
+                    self.gamepad_axes.clear();
+                }
+                _ => continue,
+            }
+            self.needs_redraw = true;
+        }
     }
 
     pub fn clear(&mut self) {
         self.keys_pressed.clear();
         self.keys_released.clear();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.next_frame();
+        }
         self.mouse_motion = [0.0; 2];
+        self.text_input.clear();
+        if self.last_mouse_position.is_some() {
+            self.last_mouse_position = Some(self.mouse_position);
+        }
         self.mouse_wheel_delta = [0.0; 2];
+        self.mouse_wheel_pixel_delta = [0.0; 2];
+        self.pinch_delta = 0.0;
+        self.rotation_delta = 0.0;
+        self.touches
+            .retain(|_, touch| !matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled));
+        // Keep press positions until the frame the button is released has been processed
+        self.mouse_press_positions
+            .retain(|button, _| self.keys_held.contains_key(&Input::Mouse(*button)));
+        self.input_windows
+            .retain(|input, _| self.keys_held.contains_key(input));
+        // Scroll ticks are only held for the update they happened in
+        self.scroll_ticks.clear();
+        let scrolls: Vec<Input> = self
+            .keys_held
+            .keys()
+            .filter(|input| matches!(input, Input::Scroll(_)))
+            .copied()
+            .collect();
+        for input in scrolls {
+            self.update_input(input, ElementState::Released);
+        }
         self.resize = None;
+        self.resizes.clear();
         self.close_requested = false;
+        self.scale_factor_changed = false;
+        self.needs_redraw = false;
+        self.first_frame = false;
+    }
+
+    /// Clears all held, pressed and released inputs along with their mouse press positions.
+    ///
+    /// Inputs that are physically held will not be reported as held again until they are pressed again.
+    pub fn reset_input(&mut self) {
+        self.keys_held.clear();
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+        self.last_press.clear();
+        self.logical_keys.clear();
+        self.input_windows.clear();
+        self.mouse_press_positions.clear();
+        self.mouse_clicks.clear();
     }
 
     /// If a key was pressed since the last update
     pub fn pressed(&self, input: &Input) -> bool {
-        self.keys_pressed.contains(input)
+        self.keys_pressed.contains_key(input)
+    }
+
+    /// The modifier keys currently held, as reported by the os
+    ///
+    /// See [`winit::event::WindowEvent::ModifiersChanged`]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// If either shift key is held
+    pub fn shift_held(&self) -> bool {
+        self.either_held(KeyCode::ShiftLeft, KeyCode::ShiftRight)
+    }
+
+    /// If either control key is held
+    pub fn ctrl_held(&self) -> bool {
+        self.either_held(KeyCode::ControlLeft, KeyCode::ControlRight)
+    }
+
+    /// If either alt key is held, the option key on macOS
+    pub fn alt_held(&self) -> bool {
+        self.either_held(KeyCode::AltLeft, KeyCode::AltRight)
+    }
+
+    /// If either super key is held, the windows key on Windows and the command key on macOS
+    pub fn super_held(&self) -> bool {
+        self.either_held(KeyCode::SuperLeft, KeyCode::SuperRight)
+    }
+
+    fn either_held(&self, left: KeyCode, right: KeyCode) -> bool {
+        self.held(&left.into()) || self.held(&right.into())
+    }
+
+    /// The modifier keys currently held, computed from the held keys.
+    ///
+    /// Unlike [`Self::modifiers`] this follows the same held state as the other inputs, so it is cleared by
+    /// [`Self::reset_input`]. Useful for matching on shortcuts:
+    ///
+    /// ```
+    /// # use rugfx::input::raw::RawInputManagerState;
+    /// use winit::keyboard::ModifiersState;
+    ///
+    /// # let input = RawInputManagerState::default();
+    /// match input.held_modifiers() {
+    ///     ModifiersState::CONTROL => println!("ctrl"),
+    ///     m if m == ModifiersState::CONTROL | ModifiersState::SHIFT => println!("ctrl + shift"),
+    ///     _ => (),
+    /// }
+    /// ```
+    pub fn held_modifiers(&self) -> ModifiersState {
+        let mut modifiers = ModifiersState::empty();
+        modifiers.set(ModifiersState::SHIFT, self.shift_held());
+        modifiers.set(ModifiersState::CONTROL, self.ctrl_held());
+        modifiers.set(ModifiersState::ALT, self.alt_held());
+        modifiers.set(ModifiersState::SUPER, self.super_held());
+        modifiers
+    }
+
+    /// Returns true if `key` was pressed since the last update while exactly `modifiers` were held.
+    ///
+    /// Useful for built in shortcuts such as debug hotkeys that shouldn't be part of the user's bindings.
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::raw::RawInputManagerState;
+    /// use winit::keyboard::{KeyCode, ModifiersState};
+    ///
+    /// # let input = RawInputManagerState::default();
+    /// if input.chord_pressed(ModifiersState::CONTROL | ModifiersState::SHIFT, KeyCode::KeyD.into()) {
+    ///     // Toggle debug overlay
+    /// }
+    /// ```
+    pub fn chord_pressed(&self, modifiers: ModifiersState, key: Input) -> bool {
+        self.modifiers == modifiers && self.pressed(&key)
+    }
+
+    /// Iterate over the inputs pressed since the last update
+    pub fn pressed_inputs(&self) -> impl Iterator<Item = &Input> {
+        self.keys_pressed.keys()
+    }
+
+    /// Returns the input pressed since the last update for a "press a key to rebind" prompt, or [`None`] if nothing was pressed.
+    ///
+    /// If several inputs were pressed the first one pressed is returned. Keys are captured as [`Input::Key`] rather than
+    /// [`Input::Logical`] as both are pressed together. To let the player cancel, check for escape before
+    /// binding the result, or use [`crate::input::rebind::RebindCapture`] which handles cancelling for you.
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::{bindings::Bindings, raw::RawInputManagerState};
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// # #[derive(Clone, PartialEq, Eq, Hash)]
+    /// # enum Binds { Jump }
+    /// # let input = RawInputManagerState::default();
+    /// # let mut bindings = Bindings::default();
+    /// if let Some(key) = input.capture_next_input() {
+    ///     if key != KeyCode::Escape.into() {
+    ///         bindings.reassign(key, Binds::Jump);
+    ///     }
+    /// }
+    /// ```
+    pub fn capture_next_input(&self) -> Option<Input> {
+        self.keys_pressed
+            .iter()
+            .filter(|(input, _)| !matches!(input, Input::Logical(_)))
+            // Inputs pressed at the same instant are ordered by name so the result doesn't depend on the hash map order
+            .min_by_key(|(input, time)| (**time, input.to_string()))
+            .map(|(input, _)| *input)
+    }
+
+    /// Iterate over the inputs currently held
+    pub fn held_inputs(&self) -> impl Iterator<Item = &Input> {
+        self.keys_held.keys()
+    }
+
+    /// Iterate over the inputs released since the last update
+    pub fn released_inputs(&self) -> impl Iterator<Item = &Input> {
+        self.keys_released.iter()
+    }
+
+    /// Returns when the input was pressed if it was pressed since the last update.
+    ///
+    /// The time is taken when the event is processed, comparing it to the time the frame is presented gives the input latency.
+    pub fn press_time(&self, input: &Input) -> Option<Instant> {
+        self.keys_pressed.get(input).copied()
+    }
+
+    /// Returns when the input was last pressed, unlike [`Self::press_time`] this is not limited to the last update.
+    pub fn last_press_time(&self, input: &Input) -> Option<Instant> {
+        self.last_press.get(input).copied()
     }
 
     /// If a key was held at all since the last update
+    ///
+    /// When the window loses focus all held keys are released, a key that is still physically held when focus returns is
+    /// not considered held until it is pressed again and its release is ignored. The modifiers are reset when focus is lost
+    /// and regained, winit reports the current modifiers again after regaining focus.
+    ///
+    /// With several windows processed by [`Self::process_window_event_for`], keys are kept held when focus moves between
+    /// them if the newly focused window reports gaining focus first. Platforms that report the focus loss first release them.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// let click = |state| WindowEvent::MouseInput {
+    ///     device_id: DeviceId::dummy(),
+    ///     state,
+    ///     button: MouseButton::Left,
+    /// };
+    /// let left = MouseButton::Left.into();
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(click(ElementState::Pressed));
+    /// input.clear();
+    ///
+    /// // Alt-tab away while holding the button
+    /// input.process_window_event(WindowEvent::Focused(false));
+    /// assert!(!input.held(&left));
+    /// assert!(input.released(&left));
+    /// input.clear();
+    ///
+    /// // Come back and let go of it
+    /// input.process_window_event(WindowEvent::Focused(true));
+    /// input.process_window_event(click(ElementState::Released));
+    /// assert!(!input.held(&left));
+    /// assert!(!input.released(&left));
+    /// input.clear();
+    /// assert_eq!(input.held_inputs().count(), 0);
+    ///
+    /// input.process_window_event(click(ElementState::Pressed));
+    /// assert!(input.pressed(&left) && input.held(&left));
+    /// ```
     pub fn held(&self, input: &Input) -> bool {
-        self.keys_held.contains(input)
+        self.keys_held.contains_key(input)
+    }
+
+    /// How long the input has been held for, or [`None`] if it isn't held. Useful for charging attacks or hold to confirm.
+    pub fn held_duration(&self, input: &Input) -> Option<Duration> {
+        self.keys_held.get(input).map(Instant::elapsed)
     }
 
     /// If a key was released since the last update
@@ -203,16 +922,345 @@ impl RawInputManagerState {
         self.keys_released.contains(input)
     }
 
+    /// The window that has keyboard focus, None if none of the windows are focused.
+    ///
+    /// Only tracked for events processed with [`Self::process_window_event_for`].
+    ///
+    /// See [`winit::event::WindowEvent::Focused`]
+    pub fn focused_window(&self) -> Option<WindowId> {
+        self.focused_window
+    }
+
+    /// The window the input was pressed in if it is held or was released since the last update.
+    ///
+    /// None for gamepad inputs and events processed without a window by [`Self::process_window_event`].
+    pub fn input_window(&self, input: &Input) -> Option<WindowId> {
+        self.input_windows.get(input).copied()
+    }
+
+    /// If a key was pressed in `window` since the last update, see [`Self::process_window_event_for`]
+    pub fn pressed_in(&self, window: WindowId, input: &Input) -> bool {
+        self.pressed(input) && self.input_window(input) == Some(window)
+    }
+
+    /// If a key that was pressed in `window` is held, see [`Self::process_window_event_for`]
+    pub fn held_in(&self, window: WindowId, input: &Input) -> bool {
+        self.held(input) && self.input_window(input) == Some(window)
+    }
+
+    /// If a key that was pressed in `window` was released since the last update, see [`Self::process_window_event_for`]
+    pub fn released_in(&self, window: WindowId, input: &Input) -> bool {
+        self.released(input) && self.input_window(input) == Some(window)
+    }
+
+    /// Copies the held, pressed and released inputs and mouse state of this update, so they can be merged for a fixed
+    /// timestep, see [`InputSnapshot`]
+    pub fn snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            held: self.keys_held.keys().copied().collect(),
+            pressed: self.keys_pressed.keys().copied().collect(),
+            released: self.keys_released.clone(),
+            mouse_motion: self.mouse_motion,
+            mouse_wheel_delta: self.mouse_wheel_delta,
+            mouse_position: self.mouse_position,
+        }
+    }
+
+    /// The text typed since the last update, following the keyboard layout and including text committed by the IME.
+    ///
+    /// Use this for text fields and consoles, and the physical keys for game controls. Control characters are not included so
+    /// check for backspace and enter with [`Self::pressed`].
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::{Ime, WindowEvent};
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(WindowEvent::Ime(Ime::Commit("こんにちは".to_owned())));
+    /// assert_eq!(input.text_input(), "こんにちは");
+    /// input.clear();
+    /// assert_eq!(input.text_input(), "");
+    /// ```
+    pub fn text_input(&self) -> &str {
+        &self.text_input
+    }
+
+    /// If the IME is enabled for the window, see [`crate::gfx::Gfx::set_ime_allowed`]
+    pub fn ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
+
+    /// The text being composed in the IME and the byte range of the cursor within it, or [`None`] if nothing is being composed.
+    ///
+    /// Draw this at the text cursor, typically underlined, so the player can see what they are typing before it is committed
+    /// to [`Self::text_input`]. The cursor is [`None`] if it should be hidden. The IME must be enabled with
+    /// [`crate::gfx::Gfx::set_ime_allowed`] to receive composition events.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::{Ime, WindowEvent};
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(WindowEvent::Ime(Ime::Enabled));
+    /// input.process_window_event(WindowEvent::Ime(Ime::Preedit("にほ".to_owned(), Some((6, 6)))));
+    /// assert_eq!(input.ime_preedit(), Some(("にほ", Some((6, 6)))));
+    /// input.process_window_event(WindowEvent::Ime(Ime::Commit("日本".to_owned())));
+    /// assert_eq!(input.ime_preedit(), None);
+    /// assert_eq!(input.text_input(), "日本");
+    /// ```
+    pub fn ime_preedit(&self) -> Option<(&str, Option<(usize, usize)>)> {
+        self.ime_preedit
+            .as_ref()
+            .map(|(text, cursor)| (text.as_str(), *cursor))
+    }
+
     /// The motion of the mouse since the last update
     pub fn mouse_motion(&self) -> [f64; 2] {
         self.mouse_motion
     }
 
-    /// Returns the mouse position relative to the current window
+    /// The mouse wheel scroll in lines since the last update
+    ///
+    /// Trackpads on macOS and Wayland scroll in pixels instead, see [`Self::mouse_wheel_pixel_delta`].
+    ///
+    /// Every whole line scrolled also presses an [`Input::Scroll`] so scrolling can be bound like a button, such as for
+    /// switching weapons. Fractions of a line are kept until they add up to a whole line. Pixel scrolling doesn't press them.
+    /// The input is pressed once per update however far it scrolled, use [`Self::scroll_ticks`] to count the lines.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::{raw::RawInputManagerState, ScrollDirection};
+    /// use winit::event::{DeviceId, MouseScrollDelta, TouchPhase, WindowEvent};
+    ///
+    /// let scroll = |y| WindowEvent::MouseWheel {
+    ///     device_id: DeviceId::dummy(),
+    ///     delta: MouseScrollDelta::LineDelta(0.0, y),
+    ///     phase: TouchPhase::Moved,
+    /// };
+    /// let up = ScrollDirection::Up.into();
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(scroll(0.5));
+    /// assert!(!input.pressed(&up));
+    /// input.process_window_event(scroll(0.5));
+    /// assert!(input.pressed(&up));
+    /// input.clear();
+    /// assert!(input.released(&up));
+    /// assert!(!input.held(&up));
+    /// ```
+    pub fn mouse_wheel_delta(&self) -> [f32; 2] {
+        self.mouse_wheel_delta
+    }
+
+    /// How many whole lines were scrolled in `direction` since the last update, each is a tick of [`Input::Scroll`].
+    ///
+    /// [`Self::pressed`] only reports one press per update, so use this to avoid skipping notches when the wheel moves several
+    /// lines in one update, such as for switching weapons.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::{raw::RawInputManagerState, ScrollDirection};
+    /// use winit::event::{DeviceId, MouseScrollDelta, TouchPhase, WindowEvent};
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(WindowEvent::MouseWheel {
+    ///     device_id: DeviceId::dummy(),
+    ///     delta: MouseScrollDelta::LineDelta(0.0, 2.0),
+    ///     phase: TouchPhase::Moved,
+    /// });
+    /// assert_eq!(input.scroll_ticks(ScrollDirection::Up), 2);
+    /// assert_eq!(input.scroll_ticks(ScrollDirection::Down), 0);
+    /// input.clear();
+    /// assert_eq!(input.scroll_ticks(ScrollDirection::Up), 0);
+    /// ```
+    pub fn scroll_ticks(&self, direction: ScrollDirection) -> u32 {
+        self.scroll_ticks.get(&direction).copied().unwrap_or(0)
+    }
+
+    /// The scroll in physical pixels since the last update, reported by trackpads and other smooth scrolling devices
+    pub fn mouse_wheel_pixel_delta(&self) -> [f64; 2] {
+        self.mouse_wheel_pixel_delta
+    }
+
+    /// The position of a gamepad axis from -1.0 to 1.0, such as a stick, or 0.0 if it hasn't moved.
+    ///
+    /// Axes from every connected gamepad are combined with the most recent change taking priority.
+    #[cfg(feature = "gamepad")]
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepad_axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Connects to the platform's gamepad backend so gamepad inputs are processed each update.
+    ///
+    /// Gamepads are not initialized by [`Default`] as it opens system devices, call this once at startup. Calling it
+    /// again replaces the backend, forgetting the state of any connected gamepads.
+    #[cfg(feature = "gamepad")]
+    pub fn init_gamepads(&mut self) -> Result<(), Box<gilrs::Error>> {
+        self.gamepads = Some(Gamepads::new()?);
+        Ok(())
+    }
+
+    /// The gamepad backend, [`None`] until [`Self::init_gamepads`] succeeds
+    #[cfg(feature = "gamepad")]
+    pub fn gamepads(&self) -> Option<&Gamepads> {
+        self.gamepads.as_ref()
+    }
+
+    /// The gamepad backend, mutable to allow rumbling with [`Gamepads::rumble`]
+    #[cfg(feature = "gamepad")]
+    pub fn gamepads_mut(&mut self) -> Option<&mut Gamepads> {
+        self.gamepads.as_mut()
+    }
+
+    /// Iterate over the active touch points, such as fingers on a touchscreen.
+    ///
+    /// Touches that ended or were cancelled are still returned for the update they ended in so their final phase can be seen.
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::raw::RawInputManagerState;
+    /// use winit::{
+    ///     dpi::PhysicalPosition,
+    ///     event::{DeviceId, Touch, TouchPhase, WindowEvent},
+    /// };
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// let touch = |phase| {
+    ///     WindowEvent::Touch(Touch {
+    ///         device_id: DeviceId::dummy(),
+    ///         phase,
+    ///         location: PhysicalPosition::new(10.0, 20.0),
+    ///         force: None,
+    ///         id: 1,
+    ///     })
+    /// };
+    /// input.process_window_event(touch(TouchPhase::Started));
+    /// assert_eq!(input.touches().next().unwrap().position, [10.0, 20.0]);
+    ///
+    /// input.process_window_event(touch(TouchPhase::Ended));
+    /// assert_eq!(input.touches().next().unwrap().phase, TouchPhase::Ended);
+    /// input.clear();
+    /// assert_eq!(input.touches().count(), 0);
+    /// ```
+    pub fn touches(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.touches.values()
+    }
+
+    /// Returns the mouse position relative to the window the cursor last moved over
     pub fn mouse_position(&self) -> [f64; 2] {
         self.mouse_position
     }
 
+    /// The last mouse position relative to `window`, None if the cursor hasn't moved over it.
+    ///
+    /// Only tracked for events processed with [`Self::process_window_event_for`].
+    pub fn mouse_position_in(&self, window: WindowId) -> Option<[f64; 2]> {
+        self.window_mouse_positions.get(&window).copied()
+    }
+
+    /// The mouse position in normalized device coordinates for a window of `size`, with y up to match wgpu's clip space.
+    ///
+    /// Returns the center if the size is zero, see [`crate::gfx::pixel_to_ndc`].
+    pub fn mouse_position_ndc(&self, size: PhysicalSize<u32>) -> [f32; 2] {
+        crate::gfx::pixel_to_ndc(self.mouse_position, size)
+    }
+
+    /// How far the cursor moved within the window since the last update, in physical pixels.
+    ///
+    /// Unlike [`Self::mouse_motion`] this is derived from the cursor position so it is available on the web and over remote
+    /// desktop, but it stops changing when the cursor is grabbed or reaches the edge of the window and jumps when the
+    /// cursor re-enters the window somewhere else. It is zero until the cursor has moved over the window.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::{dpi::PhysicalPosition, event::{DeviceId, WindowEvent}};
+    ///
+    /// let moved = |x, y| WindowEvent::CursorMoved {
+    ///     device_id: DeviceId::dummy(),
+    ///     position: PhysicalPosition::new(x, y),
+    /// };
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(moved(100.0, 50.0));
+    /// assert_eq!(input.mouse_position_delta(), [0.0, 0.0]);
+    /// input.clear();
+    /// input.process_window_event(moved(110.0, 45.0));
+    /// assert_eq!(input.mouse_position_delta(), [10.0, -5.0]);
+    /// ```
+    pub fn mouse_position_delta(&self) -> [f64; 2] {
+        let Some(last) = self.last_mouse_position else {
+            return [0.0, 0.0];
+        };
+        [
+            self.mouse_position[0] - last[0],
+            self.mouse_position[1] - last[1],
+        ]
+    }
+
+    /// Iterate over the recent cursor positions from oldest to newest along with when they were recorded.
+    ///
+    /// Only the last [`Self::cursor_history_capacity`] positions are kept, which is 0 by default so nothing is recorded.
+    /// Useful for recognising gestures such as swipes or smoothing the cursor motion.
+    pub fn cursor_history(&self) -> impl Iterator<Item = ([f64; 2], Instant)> + '_ {
+        self.cursor_history.iter().copied()
+    }
+
+    /// How many times in a row `button` was clicked with at most [`Self::multi_click_interval`] between each press.
+    ///
+    /// Check this when the button is pressed, it is 2 for a double click, 3 for a triple click and so on. Each button is counted
+    /// separately and it is 0 if the button has never been pressed.
+    ///
+    /// # Example
+    /// ```
+    /// # use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// let mut click = |button| {
+    ///     for state in [ElementState::Pressed, ElementState::Released] {
+    ///         input.process_window_event(WindowEvent::MouseInput {
+    ///             device_id: DeviceId::dummy(),
+    ///             state,
+    ///             button,
+    ///         });
+    ///     }
+    /// };
+    /// click(MouseButton::Left);
+    /// click(MouseButton::Right);
+    /// click(MouseButton::Left);
+    /// assert_eq!(input.click_count(&MouseButton::Left), 2);
+    /// assert_eq!(input.click_count(&MouseButton::Right), 1);
+    /// ```
+    pub fn click_count(&self, button: &MouseButton) -> u32 {
+        self.mouse_clicks.get(button).map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the mouse position at the moment `button` was pressed.
+    ///
+    /// Available while the button is held and on the update it is released, [`None`] otherwise.
+    pub fn press_position(&self, button: MouseButton) -> Option<[f64; 2]> {
+        self.mouse_press_positions.get(&button).copied()
+    }
+
+    /// The trackpad pinch magnification since the last update. Positive values indicate zooming in.
+    ///
+    /// Only reported on platforms that support pinch gestures (macOS and iOS), otherwise always zero.
+    ///
+    /// See [`winit::event::WindowEvent::PinchGesture`]
+    pub fn pinch_delta(&self) -> f64 {
+        self.pinch_delta
+    }
+
+    /// The trackpad rotation in degrees since the last update. Positive values indicate counterclockwise rotation.
+    ///
+    /// Only reported on platforms that support rotation gestures (macOS and iOS), otherwise always zero.
+    ///
+    /// See [`winit::event::WindowEvent::RotationGesture`]
+    pub fn rotation_delta(&self) -> f64 {
+        self.rotation_delta
+    }
+
     /// The time elapsed between the last update and the previous
     pub fn delta_time(&self) -> Duration {
         self.update_delta
@@ -240,6 +1288,92 @@ impl RawInputManagerState {
         &self.resize
     }
 
+    /// Returns every resize since the last update in the order they happened, if [`Self::accumulate_resizes`] is enabled
+    pub fn resizes(&self) -> &[PhysicalSize<u32>] {
+        &self.resizes
+    }
+
+    /// Removes and returns every resize since the last update, if [`Self::accumulate_resizes`] is enabled
+    pub fn drain_resizes(&mut self) -> std::vec::Drain<'_, PhysicalSize<u32>> {
+        self.resizes.drain(..)
+    }
+
+    /// Returns true if an input, resize or redraw request event happened since the last update, meaning the frame should be redrawn
+    ///
+    /// See [`crate::input::input_manager::InputManagerState::needs_redraw`] to combine this with your own redraw requests.
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw
+    }
+
+    /// Returns true if the window is completely hidden, for example behind other windows or on another workspace.
+    ///
+    /// Rendering can be paused while occluded to save power, a redraw is requested when the window becomes visible again.
+    /// Only reported on iOS, macOS, Web and X11, on Android, Wayland and Windows this is always false.
+    ///
+    /// See [`winit::event::WindowEvent::Occluded`]
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::event::WindowEvent;
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(WindowEvent::Occluded(true));
+    /// assert!(input.is_occluded());
+    /// assert!(!input.is_visible());
+    /// ```
+    pub fn is_occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns true if the window is minimized, inferred from the last resize being to a zero width or height.
+    ///
+    /// A zero size surface can't be configured so rendering should be skipped until the window is restored, which is reported
+    /// as another resize. Platforms that keep the size when minimizing report it as [`Self::is_occluded`] instead, if at all.
+    ///
+    /// # Example
+    /// ```
+    /// use rugfx::input::raw::RawInputManagerState;
+    /// use winit::{dpi::PhysicalSize, event::WindowEvent};
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.process_window_event(WindowEvent::Resized(PhysicalSize::new(0, 0)));
+    /// assert!(input.is_minimized());
+    /// assert!(!input.is_visible());
+    /// input.process_window_event(WindowEvent::Resized(PhysicalSize::new(800, 600)));
+    /// assert!(input.is_visible());
+    /// ```
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    /// Returns true unless the window is [`Self::is_occluded`] or [`Self::is_minimized`], check this before drawing and presenting
+    pub fn is_visible(&self) -> bool {
+        !self.occluded && !self.minimized
+    }
+
+    /// The ratio of physical pixels to logical pixels of the window, such as 2.0 on a high DPI display.
+    ///
+    /// Positions such as [`Self::mouse_position`] are in physical pixels, divide by this to get logical pixels. winit only
+    /// reports changes so this is 1.0 until the first change, initialize it with [`Self::set_scale_factor`] and
+    /// [`winit::window::Window::scale_factor`] after creating the window.
+    ///
+    /// See [`winit::event::WindowEvent::ScaleFactorChanged`]
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Sets the scale factor, such as to the initial [`winit::window::Window::scale_factor`]. Does not count as a change.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+    }
+
+    /// Returns true if the scale factor changed since the last update, such as when the window moved to another monitor, so
+    /// the UI should be laid out again
+    pub fn scale_factor_changed(&self) -> bool {
+        self.scale_factor_changed
+    }
+
     /// Returns true if the os/window manager has requested the window close, normally by clicking the close button
     ///
     /// See [`winit::event::WindowEvent::CloseRequested`]
@@ -247,6 +1381,13 @@ impl RawInputManagerState {
         self.close_requested
     }
 
+    /// Returns true during the first update, useful for one time setup that needs the timing or input state.
+    ///
+    /// The first update happens on [`StartCause::Init`] before the window is created and is not followed by a draw.
+    pub fn is_first_frame(&self) -> bool {
+        self.first_frame
+    }
+
     /// Returns true if the winit event loop was destroyed.
     ///
     /// See [`winit::event::Event::LoopExiting`].
@@ -254,15 +1395,69 @@ impl RawInputManagerState {
         self.loop_exiting
     }
 
-    /// The total time since the start of the game
+    /// The wall-clock time since the start of the game, measured at the last update.
+    ///
+    /// This only changes once per update, use [`Self::real_time`] for the time at the moment it is called. It keeps advancing
+    /// while the game is paused and ignores the time scale, use [`crate::input::input_manager::InputManagerState::game_time`]
+    /// for a clock that is scaled by the `time_scale` and stops when it is 0.
     pub fn game_time(&self) -> Duration {
         self.last_update.saturating_duration_since(self.start)
     }
 
+    /// Restarts [`Self::game_time`] and [`Self::real_time`] from the last update, such as when starting a new level.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rugfx::input::raw::RawInputManagerState;
+    ///
+    /// let mut input = RawInputManagerState::default();
+    /// input.preupdate();
+    /// input.reset_game_time();
+    /// assert_eq!(input.game_time(), Duration::ZERO);
+    /// ```
+    pub fn reset_game_time(&mut self) {
+        self.start = self.last_update;
+        self.interval_timers.clear();
+    }
+
+    /// The total time since the start of the game, measured when called.
+    ///
+    /// Unlike [`Self::game_time`] this isn't quantized to updates, so it keeps advancing between them.
+    pub fn real_time(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns true once for every `duration` of wall-clock [`Self::game_time`] that elapses, call it every update with the same duration.
+    ///
+    /// The first interval starts at the first call with each duration. If an update is longer than the interval, the
+    /// missed intervals are returned on the following calls, so none are skipped. A zero duration returns true every call.
+    pub fn tick_every(&mut self, duration: Duration) -> bool {
+        let now = self.game_time();
+        tick_interval(&mut self.interval_timers, duration, now)
+    }
+
     /// Runs every duration
     #[cfg(feature = "unstable")]
+    #[deprecated(
+        note = "skips or repeats intervals depending on the frame rate, use `tick_every` instead"
+    )]
     pub fn every(&self, duration: Duration) -> bool {
         let game_time = self.game_time();
         game_time.as_secs_f64() % duration.as_secs_f64() < self.update_delta.as_secs_f64()
     }
 }
+
+/// Adds a delta to a total ignoring non-finite deltas and clamping the total to the limit
+fn accumulate(total: &mut f64, delta: f64, limit: f64) {
+    if delta.is_finite() {
+        *total = (*total + delta).clamp(-limit, limit);
+    }
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn accumulate_f32(total: &mut f32, delta: f32, limit: f64) {
+    let mut total_f64 = f64::from(*total);
+    accumulate(&mut total_f64, f64::from(delta), limit);
+    *total = total_f64 as f32;
+}
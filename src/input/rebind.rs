@@ -0,0 +1,74 @@
+use winit::keyboard::KeyCode;
+
+use super::{raw::RawInputManagerState, Input};
+
+/// The state of a [`RebindCapture`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RebindState {
+    /// Still waiting for an input
+    Pending,
+    /// An input was pressed that can be bound
+    Captured(Input),
+    /// The cancel input was pressed
+    Cancelled,
+}
+
+/// Waits for the player to press the input they want to bind, or the cancel input to abort, for a settings menu.
+///
+/// Create one when the rebind prompt opens and call [`Self::update`] every update until it is no longer
+/// [`RebindState::Pending`]. The first update is always pending so the click or key press that opened the prompt isn't captured.
+///
+/// # Example
+/// ```
+/// use rugfx::input::{
+///     raw::RawInputManagerState,
+///     rebind::{RebindCapture, RebindState},
+/// };
+///
+/// # let input = RawInputManagerState::default();
+/// let mut capture = RebindCapture::default();
+/// match capture.update(&input) {
+///     RebindState::Pending => (),
+///     RebindState::Captured(key) => println!("Bound to {key}"),
+///     RebindState::Cancelled => println!("Cancelled"),
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RebindCapture {
+    /// The input that cancels the capture. Defaults to escape.
+    pub cancel: Input,
+    armed: bool,
+}
+
+impl Default for RebindCapture {
+    fn default() -> Self {
+        Self {
+            cancel: KeyCode::Escape.into(),
+            armed: false,
+        }
+    }
+}
+
+impl RebindCapture {
+    /// Initialize a new capture with a cancel input
+    pub fn new(cancel: Input) -> Self {
+        Self {
+            cancel,
+            ..Default::default()
+        }
+    }
+
+    /// Call this every update, if several inputs were pressed in the same update the earliest is captured
+    pub fn update(&mut self, input: &RawInputManagerState) -> RebindState {
+        if !self.armed {
+            self.armed = true;
+            return RebindState::Pending;
+        }
+        if input.pressed(&self.cancel) {
+            return RebindState::Cancelled;
+        }
+        input
+            .capture_next_input()
+            .map_or(RebindState::Pending, RebindState::Captured)
+    }
+}
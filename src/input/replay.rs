@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant};
+
+use super::{raw::RawInputManagerState, Input};
+
+/// A single press or release recorded by [`RawInputManagerState::start_recording`].
+///
+/// With the `serde` feature records can be saved to a file, such as to attach to a bug report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputRecord {
+    /// The number of updates between the recording starting and this input
+    pub frame: u64,
+    /// The real time between the recording starting and this input
+    pub time: Duration,
+    /// The input that changed
+    pub input: Input,
+    /// True if the input was pressed, false if it was released
+    pub pressed: bool,
+}
+
+/// Records inputs as they change, owned by [`RawInputManagerState`] while recording
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    records: Vec<InputRecord>,
+    frame: u64,
+    start: Instant,
+}
+
+impl Recorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            frame: 0,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, input: Input, pressed: bool) {
+        self.records.push(InputRecord {
+            frame: self.frame,
+            time: self.start.elapsed(),
+            input,
+            pressed,
+        });
+    }
+
+    pub(crate) fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    pub(crate) fn finish(self) -> Vec<InputRecord> {
+        self.records
+    }
+}
+
+/// Plays back inputs recorded with [`RawInputManagerState::start_recording`], one frame per update.
+///
+/// Use [`crate::input::raw::RawInputManager::replay`] to replay through the event loop, or call [`Self::apply`] every update
+/// when driving [`RawInputManagerState`] yourself. Presses and releases are replayed, including [`Input::Scroll`] ticks, but
+/// mouse motion, the mouse wheel deltas and text are not.
+///
+/// # Example
+/// ```
+/// use rugfx::input::{raw::RawInputManagerState, replay::InputReplay};
+/// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+///
+/// let click = |state| WindowEvent::MouseInput {
+///     device_id: DeviceId::dummy(),
+///     state,
+///     button: MouseButton::Left,
+/// };
+/// let mut input = RawInputManagerState::default();
+/// input.start_recording();
+/// input.process_window_event(click(ElementState::Pressed));
+/// input.clear();
+/// input.clear();
+/// input.process_window_event(click(ElementState::Released));
+/// input.clear();
+/// let records = input.stop_recording();
+///
+/// let mut replayed = RawInputManagerState::default();
+/// let mut replay = InputReplay::new(records);
+/// let left = MouseButton::Left.into();
+/// let mut frames = Vec::new();
+/// while !replay.is_finished() {
+///     replay.apply(&mut replayed);
+///     frames.push((replayed.pressed(&left), replayed.held(&left), replayed.released(&left)));
+///     replayed.clear();
+/// }
+/// assert_eq!(frames, [(true, true, false), (false, true, false), (false, false, true)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputReplay {
+    records: Vec<InputRecord>,
+    next: usize,
+    frame: u64,
+}
+
+impl InputReplay {
+    /// Initialize a replay of recorded inputs, which must be in the order they were recorded
+    pub fn new(records: Vec<InputRecord>) -> Self {
+        Self {
+            records,
+            next: 0,
+            frame: 0,
+        }
+    }
+
+    /// Applies the inputs of the next frame, call this once per update after processing events and before reading the state
+    pub fn apply(&mut self, state: &mut RawInputManagerState) {
+        while let Some(record) = self
+            .records
+            .get(self.next)
+            .filter(|record| record.frame <= self.frame)
+        {
+            state.replay_record(record);
+            self.next += 1;
+        }
+        self.frame += 1;
+    }
+
+    /// Returns true once every recorded input has been applied
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.records.len()
+    }
+}
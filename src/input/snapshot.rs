@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use super::Input;
+
+/// A copy of the input state of one update, see [`crate::input::raw::RawInputManagerState::snapshot`].
+///
+/// Snapshots can be merged so a fixed timestep that runs less often than the frame rate still sees every press and release.
+/// Merge a snapshot every update and clear the edges after each tick.
+///
+/// # Example
+/// ```
+/// use rugfx::input::{raw::RawInputManagerState, snapshot::InputSnapshot};
+/// use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+///
+/// let click = |state| WindowEvent::MouseInput {
+///     device_id: DeviceId::dummy(),
+///     state,
+///     button: MouseButton::Left,
+/// };
+/// let mut input = RawInputManagerState::default();
+/// let mut pending = InputSnapshot::default();
+///
+/// // A click that starts and ends between two ticks
+/// input.process_window_event(click(ElementState::Pressed));
+/// pending.merge(input.snapshot());
+/// input.clear();
+/// input.process_window_event(click(ElementState::Released));
+/// pending.merge(input.snapshot());
+/// input.clear();
+///
+/// // The tick sees both the press and the release
+/// assert!(pending.pressed(&MouseButton::Left.into()));
+/// assert!(pending.released(&MouseButton::Left.into()));
+/// assert!(!pending.held(&MouseButton::Left.into()));
+/// pending.clear_edges();
+/// assert!(!pending.pressed(&MouseButton::Left.into()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputSnapshot {
+    /// Inputs held at the time of the latest snapshot
+    pub held: HashSet<Input>,
+    /// Inputs pressed in any of the merged snapshots
+    pub pressed: HashSet<Input>,
+    /// Inputs released in any of the merged snapshots
+    pub released: HashSet<Input>,
+    /// The mouse motion summed over the merged snapshots
+    pub mouse_motion: [f64; 2],
+    /// The mouse wheel scroll in lines summed over the merged snapshots
+    pub mouse_wheel_delta: [f32; 2],
+    /// The mouse position at the time of the latest snapshot
+    pub mouse_position: [f64; 2],
+}
+
+impl InputSnapshot {
+    /// If the input was pressed in any of the merged snapshots
+    pub fn pressed(&self, input: &Input) -> bool {
+        self.pressed.contains(input)
+    }
+
+    /// If the input is held as of the latest snapshot
+    pub fn held(&self, input: &Input) -> bool {
+        self.held.contains(input)
+    }
+
+    /// If the input was released in any of the merged snapshots
+    pub fn released(&self, input: &Input) -> bool {
+        self.released.contains(input)
+    }
+
+    /// Combines a newer snapshot into this one, keeping the presses and releases of both and summing the deltas.
+    ///
+    /// The held inputs and mouse position are replaced by those of the newer snapshot.
+    pub fn merge(&mut self, newer: Self) {
+        self.held = newer.held;
+        self.pressed.extend(newer.pressed);
+        self.released.extend(newer.released);
+        self.mouse_motion[0] += newer.mouse_motion[0];
+        self.mouse_motion[1] += newer.mouse_motion[1];
+        self.mouse_wheel_delta[0] += newer.mouse_wheel_delta[0];
+        self.mouse_wheel_delta[1] += newer.mouse_wheel_delta[1];
+        self.mouse_position = newer.mouse_position;
+    }
+
+    /// Clears the presses, releases and deltas once they have been consumed, keeping the held inputs and mouse position
+    pub fn clear_edges(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.mouse_motion = [0.0; 2];
+        self.mouse_wheel_delta = [0.0; 2];
+    }
+}
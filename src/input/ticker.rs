@@ -11,6 +11,11 @@ pub struct Ticker {
     is_tick: bool,
     /// Determines if ticks occur. Set to true to pause ticks, when set to false the next tick will most likely be instant.
     pub paused: bool,
+    /// The most ticks [`Self::pending_ticks`] returns at once, any further time is dropped so a slow update doesn't cause
+    /// even more ticks the next update. Defaults to 8.
+    pub max_ticks_per_update: u32,
+    accumulator: Duration,
+    last_accumulate: Instant,
 }
 
 impl Default for Ticker {
@@ -21,6 +26,9 @@ impl Default for Ticker {
             last: Instant::now(),
             is_tick: false,
             paused: false,
+            max_ticks_per_update: 8,
+            accumulator: Duration::ZERO,
+            last_accumulate: Instant::now(),
         }
     }
 }
@@ -44,6 +52,71 @@ impl Ticker {
         }
     }
 
+    /// Call this every update instead of [`Self::update`] to run a fixed timestep, returns the number of ticks to run.
+    ///
+    /// Time since the previous call is added to an accumulator and a tick is taken out for every whole interval, so a slow
+    /// update produces several ticks to catch up rather than losing them. At most [`Self::max_ticks_per_update`] ticks are
+    /// returned and [`Self::count`] is increased by the number of ticks. A zero interval produces one tick per call.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use rugfx::input::ticker::Ticker;
+    ///
+    /// let mut ticker = Ticker::new(Duration::from_millis(1));
+    /// ticker.max_ticks_per_update = 3;
+    /// std::thread::sleep(Duration::from_millis(10));
+    /// let ticks = ticker.pending_ticks();
+    /// for _ in 0..ticks {
+    ///     // Fixed update
+    /// }
+    /// assert_eq!(ticks, 3);
+    /// assert_eq!(ticker.count, 3);
+    /// assert!((0.0..=1.0).contains(&ticker.interpolation_alpha()));
+    /// ```
+    pub fn pending_ticks(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.last_accumulate);
+        self.last_accumulate = now;
+        if self.paused {
+            return 0;
+        }
+        self.accumulator += elapsed;
+        let ticks = if self.interval.is_zero() {
+            self.accumulator = Duration::ZERO;
+            1.min(self.max_ticks_per_update)
+        } else {
+            let whole = self.accumulator.as_nanos() / self.interval.as_nanos();
+            let ticks = u32::try_from(whole)
+                .unwrap_or(u32::MAX)
+                .min(self.max_ticks_per_update);
+            if u128::from(ticks) < whole {
+                // Drop the time that can't be caught up, keeping the progress through the current tick
+                self.accumulator = Duration::from_nanos(
+                    u64::try_from(self.accumulator.as_nanos() % self.interval.as_nanos())
+                        .unwrap_or_default(),
+                );
+            } else {
+                self.accumulator -= self.interval * ticks;
+            }
+            ticks
+        };
+        self.count += ticks as usize;
+        ticks
+    }
+
+    /// The progress through the current tick from the [`Self::pending_ticks`] accumulator, from 0.0 to 1.0.
+    ///
+    /// Use this to interpolate rendering between the previous and current fixed update state. Unlike
+    /// [`Self::tick_ratio_from_last_tick`] it only changes when [`Self::pending_ticks`] is called so it is consistent for the
+    /// whole frame.
+    pub fn interpolation_alpha(&self) -> f32 {
+        if self.interval.is_zero() {
+            return 0.0;
+        }
+        (self.accumulator.as_secs_f32() / self.interval.as_secs_f32()).clamp(0.0, 1.0)
+    }
+
     /// Returns whether this update is a tick
     pub fn is_tick(&self) -> bool {
         self.is_tick
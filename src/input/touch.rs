@@ -0,0 +1,46 @@
+use winit::event::{Force, Touch, TouchPhase};
+
+/// A single touch point, such as a finger or a stylus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    /// Unique identifier of the finger or stylus
+    pub id: u64,
+    /// The position relative to the current window
+    pub position: [f64; 2],
+    /// The phase of the touch
+    pub phase: TouchPhase,
+    /// How hard the screen was pressed normalized to the range 0.0 to 1.0 inclusive.
+    ///
+    /// [`None`] if the platform does not support pressure sensitivity. Only available on iOS, Windows, Web and Android.
+    /// On Android this is never [`None`] but devices without pressure sensitivity only report 0.0 or 1.0.
+    pub pressure: Option<f32>,
+    /// The altitude of a stylus in radians, 0 is parallel to the surface and pi/2 is perpendicular.
+    ///
+    /// Only available for the Apple Pencil on iOS.
+    pub altitude_angle: Option<f64>,
+}
+
+impl From<&Touch> for TouchPoint {
+    fn from(touch: &Touch) -> Self {
+        Self {
+            id: touch.id,
+            position: [touch.location.x, touch.location.y],
+            phase: touch.phase,
+            pressure: touch.force.as_ref().map(normalized_pressure),
+            altitude_angle: match touch.force {
+                Some(Force::Calibrated { altitude_angle, .. }) => altitude_angle,
+                _ => None,
+            },
+        }
+    }
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn normalized_pressure(force: &Force) -> f32 {
+    let pressure = force.normalized();
+    if pressure.is_finite() {
+        pressure.clamp(0.0, 1.0) as f32
+    } else {
+        0.0
+    }
+}
@@ -1,5 +1,12 @@
 pub mod gfx;
 pub mod input;
 
+pub use gfx::{Gfx, GfxConfig, GfxError};
+pub use input::{
+    bindings::{AxisBind, Bindings, InputBind},
+    input_manager::InputManagerState,
+    raw::{RawInputHandler, RawInputManager, RawInputManagerState},
+    Input, InputDevice,
+};
 pub use wgpu;
 pub use winit;
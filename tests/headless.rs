@@ -0,0 +1,182 @@
+#![allow(clippy::unwrap_used, clippy::print_stderr)]
+
+use std::num::NonZeroU32;
+
+use rugfx::gfx::{Gfx, GfxBacking, GfxConfig, GfxError};
+use winit::dpi::PhysicalSize;
+
+const TRIANGLE_SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the bottom left half of the screen, its diagonal edge crosses pixels partially
+    var positions = array<vec2<f32>, 3>(vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0));
+    return vec4(positions[index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4(1.0, 1.0, 1.0, 1.0);
+}
+";
+
+const CLEAR_COLOR: wgpu::Color = wgpu::Color::RED;
+const EXPECTED_PIXEL: [u8; 4] = [255, 0, 0, 255];
+
+/// Creates a buffer backed [`Gfx`], or [`None`] to skip the test on machines without any adapter
+fn headless_gfx(size: PhysicalSize<u32>, config: &GfxConfig) -> Option<Gfx> {
+    match Gfx::new_from_buffer(size, config) {
+        Ok(gfx) => Some(gfx),
+        Err(GfxError::RequestAdapterError) => {
+            eprintln!("Skipping, no adapter is available");
+            None
+        }
+        Err(err) => panic!("failed to create gfx: {err}"),
+    }
+}
+
+fn headless_config() -> GfxConfig {
+    GfxConfig {
+        // Headless rendering doesn't need any optional features, which keeps software adapters usable
+        required_features: wgpu::Features::empty(),
+        ..Default::default()
+    }
+}
+
+fn buffer_size(gfx: &Gfx) -> PhysicalSize<u32> {
+    let GfxBacking::Buffer(buffer) = &gfx.backing else {
+        unreachable!("created from a buffer")
+    };
+    PhysicalSize::new(buffer.extent.width, buffer.extent.height)
+}
+
+fn clear_and_check(gfx: &Gfx, size: PhysicalSize<u32>) {
+    let texture = gfx.get_current_texture().unwrap();
+    let view = texture
+        .texture()
+        .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = gfx.create_encoder(Some("clear"));
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(gfx.clear_attachment(CLEAR_COLOR, &view).unwrap())],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    gfx.submit(Some(encoder.finish()));
+    gfx.present().unwrap();
+
+    let pixels = gfx.read_pixels().unwrap();
+    assert_eq!(pixels.len(), (size.width * size.height * 4) as usize);
+    for pixel in pixels.chunks_exact(4) {
+        assert_eq!(pixel, EXPECTED_PIXEL);
+    }
+}
+
+#[test]
+fn clear_reads_back() {
+    // Width is deliberately not a multiple of 64 so the rows of the readback buffer are padded
+    let size = PhysicalSize::new(100, 50);
+    let Some(gfx) = headless_gfx(size, &headless_config()) else {
+        return;
+    };
+    clear_and_check(&gfx, size);
+}
+
+#[test]
+fn zero_resize_is_ignored() {
+    let size = PhysicalSize::new(100, 50);
+    let Some(mut gfx) = headless_gfx(size, &headless_config()) else {
+        return;
+    };
+    // Minimizing delivers a zero size which must be ignored until the next real resize
+    let count = gfx.set_multisample_count(NonZeroU32::new(4).unwrap());
+    gfx.window_resize(&PhysicalSize::new(0, 0));
+    assert_eq!(buffer_size(&gfx), size);
+
+    let restored = PhysicalSize::new(60, 20);
+    gfx.window_resize(&restored);
+    assert_eq!(buffer_size(&gfx), restored);
+    assert_eq!(
+        (gfx.config.width, gfx.config.height),
+        (restored.width, restored.height)
+    );
+    assert_eq!(gfx.multisample_view.is_some(), count.get() > 1);
+    // Resolving a multisample view of the wrong size into the buffer texture fails validation
+    clear_and_check(&gfx, restored);
+}
+
+#[test]
+fn msaa_triangle_is_antialiased() {
+    let size = PhysicalSize::new(64, 64);
+    let config = GfxConfig {
+        multisample_count: NonZeroU32::new(4).unwrap(),
+        ..headless_config()
+    };
+    let Some(gfx) = headless_gfx(size, &config) else {
+        return;
+    };
+    assert_eq!(gfx.multisample_count.get(), 4, "4x msaa is not supported");
+
+    let shader = gfx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("triangle shader"),
+            source: wgpu::ShaderSource::Wgsl(TRIANGLE_SHADER.into()),
+        });
+    let pipeline = gfx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("triangle pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(gfx.config.format.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: gfx.multisample_count.get(),
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+    let texture = gfx.get_current_texture().unwrap();
+    let view = texture
+        .texture()
+        .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = gfx.create_encoder(Some("triangle"));
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(
+                gfx.clear_attachment(wgpu::Color::BLACK, &view).unwrap(),
+            )],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.draw(0..3, 0..1);
+    }
+    gfx.submit(Some(encoder.finish()));
+
+    let (pixels, width, height) = gfx.capture_rgba().unwrap();
+    assert_eq!((width, height), (size.width, size.height));
+    let count = |f: fn(u8) -> bool| pixels.chunks_exact(4).filter(|p| f(p[0])).count();
+    assert!(count(|r| r == 255) > 0, "triangle was not drawn");
+    assert!(count(|r| r == 0) > 0, "triangle covers the whole buffer");
+    assert!(
+        count(|r| r > 0 && r < 255) > 0,
+        "edges are not anti-aliased"
+    );
+}